@@ -1,4 +1,3 @@
-use std::fmt;
 use std::os::unix::io::AsRawFd;
 
 use mio::{unix::SourceFd, Events, Interest, Poll, Token};
@@ -67,10 +66,6 @@ fn main() -> Result<(), Error> {
                                                 bt_mgmt::Appearance::from(appearance_identifier);
                                             match appearance {
                                                 bt_mgmt::Appearance::Reserved => {
-                                                    fmt::format(format_args!(
-                                                        "Hello, {}!",
-                                                        "world"
-                                                    ));
                                                     print!(
                                                         " appearance reserved {:04x}",
                                                         appearance_identifier
@@ -1,4 +1,3 @@
-use std::fmt;
 use std::os::unix::io::AsRawFd;
 use std::time::Duration;
 
@@ -10,10 +9,12 @@ use byteorder::{ByteOrder, LittleEndian};
 
 use bt_mgmt::{
     self,
+    common::DeviceId,
     eir::{self, EirEntry},
     events::{self, EventId},
+    operations::{ConfirmName, StartDiscovery, StopDiscovery},
     pack::{Unpack, UnpackFixed},
-    ClassOfDevice, Error, HardwareAddress, OperationId, Socket, Status,
+    ClassOfDevice, Error, HardwareAddress, OperationId, Socket, Uuid,
 };
 
 const MGMT_EVENTS: Token = Token(0);
@@ -90,13 +91,9 @@ impl Scanner {
         match event {
             EventId::CommandComplete => {
                 let (complete, _) = events::CommandComplete::unpack(&buffer[..size])?;
-                if complete.status == Status::Success {
-                    self.event_command_complete(index, complete.operation, complete.data)?;
-                } else {
-                    println!(
-                        "Command failed {} {:?} {:?}",
-                        index, complete.operation, complete.status
-                    );
+                match complete.parameters() {
+                    Ok(data) => self.event_command_complete(index, complete.operation, data)?,
+                    Err(err) => println!("Command {} {:?} {}", index, complete.operation, err),
                 }
             }
             EventId::ClassOfDeviceChanged => {
@@ -123,17 +120,18 @@ impl Scanner {
 
     fn time(&mut self) -> Result<(), Error> {
         if self.mgmt_index < 0xffff {
-            let discovering_type = (events::DiscoveringType::BR_EDR
+            let discovering_type = events::DiscoveringType::BR_EDR
                 | events::DiscoveringType::LE_PUBLIC
-                | events::DiscoveringType::LE_RANDOM)
-                .bits();
+                | events::DiscoveringType::LE_RANDOM;
             if self.scanning {
-                println!("Stop Discovering {:02x}", discovering_type);
-                self.send_command(OperationId::StopDiscovery, &[discovering_type])?;
+                println!("Stop Discovering {:?}", discovering_type);
+                self.mgmt
+                    .send(self.mgmt_index, &StopDiscovery::new(discovering_type))?;
                 self.scanning = false;
             } else {
-                println!("Start Discovering {:02x}", discovering_type);
-                self.send_command(OperationId::StartDiscovery, &[discovering_type])?;
+                println!("Start Discovering {:?}", discovering_type);
+                self.mgmt
+                    .send(self.mgmt_index, &StartDiscovery::new(discovering_type))?;
                 self.scanning = true;
             }
         }
@@ -162,7 +160,7 @@ impl Scanner {
                 if (data.len() - offset) >= octets {
                     let indicies: Vec<u16> = data[offset..offset + octets]
                         .chunks_exact(2)
-                        .map(|c| LittleEndian::read_u16(c))
+                        .map(LittleEndian::read_u16)
                         .collect();
                     println!("Index List, {:?}", indicies);
                     if self.mgmt_index > 0x7fff && indicies.len() == 1 {
@@ -208,6 +206,9 @@ impl Scanner {
             "Event {} Device found {} {:4} {:08x}",
             index, device_found.address_info, device_found.rssi, device_found.flags
         );
+        if let Some(confirm) = ConfirmName::from_device_found(&device_found, |_| false)? {
+            self.mgmt.send(index, &confirm)?;
+        }
         let length = device_found.data.len();
         let mut offset = 0usize;
         while offset < length {
@@ -232,7 +233,6 @@ impl Scanner {
                         let appearance = bt_mgmt::Appearance::from(appearance_identifier);
                         match appearance {
                             bt_mgmt::Appearance::Reserved => {
-                                fmt::format(format_args!("Hello, {}!", "world"));
                                 print!(" appearance reserved {:04x}", appearance_identifier);
                             }
                             _ => {
@@ -247,6 +247,10 @@ impl Scanner {
                     let cod = bt_mgmt::ClassOfDevice::unpack(&eir.data[..3])?;
                     print!(" class {:?}", cod.device_class());
                 }
+                eir::DataType::SecurityManagerTemporaryKey => match DeviceId::from_eir(&eir) {
+                    Some(device_id) => print!(" device id {:?}", device_id),
+                    None => print!(" {:?} ({})", eir.data_type, eir.data.len()),
+                },
                 eir::DataType::ShortenedLocalName | eir::DataType::CompleteLocalName => {
                     match std::str::from_utf8(eir.data) {
                         Ok(name) => {
@@ -261,16 +265,21 @@ impl Scanner {
                 | eir::DataType::CompleteServiceClassUUIDs16 => {
                     print!(" UUID");
                     for chunk in eir.data.chunks_exact(2) {
-                        let uuid = LittleEndian::read_u16(chunk);
-                        print!(" {:04x}", uuid);
+                        print!(" {}", Uuid::unpack(chunk)?);
                     }
                 }
                 eir::DataType::IncompleteServiceClassUUIDs32
                 | eir::DataType::CompleteServiceClassUUIDs32 => {
                     print!(" UUID");
                     for chunk in eir.data.chunks_exact(4) {
-                        let uuid = LittleEndian::read_u32(chunk);
-                        print!(" {:08x}", uuid);
+                        print!(" {}", Uuid::unpack(chunk)?);
+                    }
+                }
+                eir::DataType::IncompleteServiceClassUUIDs128
+                | eir::DataType::CompleteServiceClassUUIDs128 => {
+                    print!(" UUID");
+                    for chunk in eir.data.chunks_exact(16) {
+                        print!(" {}", Uuid::unpack(chunk)?);
                     }
                 }
                 _ => {
@@ -4,7 +4,12 @@ use std::fmt;
 use crate::extended_enum;
 use crate::hardware_address::HardwareAddress;
 
-use crate::{error::HciError, error::HciErrorKind, pack::UnpackFixed, Error};
+use crate::{
+    error::HciError,
+    error::HciErrorKind,
+    pack::{PackFixed, UnpackFixed},
+    Error,
+};
 
 // Basic Rate / Enhanced Data Rate (BR/EDR)
 //
@@ -31,12 +36,48 @@ impl fmt::Display for AddressType {
     }
 }
 
+bitflags!(
+    /// Mask of address types, the bit of each type is its value
+    pub struct AddressTypes: u8 {
+        const BR_EDR = 0b001;
+        const LE_PUBLIC = 0b010;
+        const LE_RANDOM = 0b100;
+        const LE = Self::LE_PUBLIC.bits | Self::LE_RANDOM.bits;
+    }
+);
+
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub struct AddressInfo {
     pub address: HardwareAddress,
     pub address_type: AddressType,
 }
 
+impl AddressInfo {
+    /// Size of a packed address information in octets
+    pub const SIZE: usize = 7;
+
+    /// Check if this is an LE identity address, either a public address or a
+    /// static random address
+    pub fn is_identity(&self) -> bool {
+        match self.address_type {
+            AddressType::BrEdr => false,
+            AddressType::LePublic => true,
+            AddressType::LeRandom => self.address.is_static_random(),
+        }
+    }
+}
+
+impl PackFixed<AddressInfo, Error> for AddressInfo {
+    fn pack(&self, data: &mut [u8]) -> Result<(), Error> {
+        if data.len() != AddressInfo::SIZE {
+            return Err(Error::from(HciError::new(HciErrorKind::InvalidLength)));
+        }
+        data[0..6].copy_from_slice(&self.address.bytes());
+        data[6] = u8::from(self.address_type);
+        Ok(())
+    }
+}
+
 impl<'a> UnpackFixed<'a, AddressInfo, Error> for AddressInfo {
     fn unpack(data: &'a [u8]) -> Result<AddressInfo, Error> {
         if data.len() != 7 {
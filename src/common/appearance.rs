@@ -1,4 +1,6 @@
-use core::convert::From;
+use core::convert::{From, TryFrom};
+
+use crate::{error::HciError, error::HciErrorKind, Error};
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum Watch {
@@ -73,6 +75,7 @@ pub enum GenericNetworkDevice {
     AccessPoint,
 }
 
+#[allow(clippy::enum_variant_names)]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum Sensor {
     Generic,
@@ -254,7 +257,7 @@ impl From<u16> for Appearance {
             (22, 18) => Appearance::LightFixture(LightFixture::ShelvesLight),
             (22, 19) => Appearance::LightFixture(LightFixture::HighBayLowBayLight),
             (22, 20) => Appearance::LightFixture(LightFixture::EmergencyExitLight),
-            (22, 0) => Appearance::Fan(Fan::Generic),
+            (22, 0) => Appearance::LightFixture(LightFixture::Generic),
             (23, 1) => Appearance::Fan(Fan::Ceiling),
             (23, 2) => Appearance::Fan(Fan::Axial),
             (23, 3) => Appearance::Fan(Fan::Exhaust),
@@ -266,3 +269,140 @@ impl From<u16> for Appearance {
         }
     }
 }
+
+impl TryFrom<Appearance> for u16 {
+    type Error = Error;
+
+    fn try_from(value: Appearance) -> Result<Self, Self::Error> {
+        let (category, sub_category) = match value {
+            Appearance::Unknown => (0, 0),
+            Appearance::Phone => (1, 0),
+            Appearance::Computer => (2, 0),
+            Appearance::Watch(Watch::Sport) => (3, 1),
+            Appearance::Watch(Watch::Generic) => (3, 0),
+            Appearance::Clock => (4, 0),
+            Appearance::Display => (5, 0),
+            Appearance::RemoteControl => (6, 0),
+            Appearance::EyeGlasses => (7, 0),
+            Appearance::Tag => (8, 0),
+            Appearance::Keyring => (9, 0),
+            Appearance::MediaPlayer => (10, 0),
+            Appearance::BarcodeScanner => (11, 0),
+            Appearance::Thermometer(Thermometer::Ear) => (12, 1),
+            Appearance::Thermometer(Thermometer::Generic) => (12, 0),
+            Appearance::HeartRateSensor(HeartRateSensor::Belt) => (13, 1),
+            Appearance::HeartRateSensor(HeartRateSensor::Generic) => (13, 0),
+            Appearance::BloodPressure(BloodPressure::Arm) => (14, 1),
+            Appearance::BloodPressure(BloodPressure::Wrist) => (14, 2),
+            Appearance::BloodPressure(BloodPressure::Generic) => (14, 0),
+            Appearance::HumanInterfaceDevice(HumanInterfaceDevice::Keyboard) => (15, 1),
+            Appearance::HumanInterfaceDevice(HumanInterfaceDevice::Mouse) => (15, 2),
+            Appearance::HumanInterfaceDevice(HumanInterfaceDevice::Joystick) => (15, 3),
+            Appearance::HumanInterfaceDevice(HumanInterfaceDevice::Gamepad) => (15, 4),
+            Appearance::HumanInterfaceDevice(HumanInterfaceDevice::DigitizerTablet) => (15, 5),
+            Appearance::HumanInterfaceDevice(HumanInterfaceDevice::CardReader) => (15, 6),
+            Appearance::HumanInterfaceDevice(HumanInterfaceDevice::DigitalPen) => (15, 7),
+            Appearance::HumanInterfaceDevice(HumanInterfaceDevice::BarcodeScanner) => (15, 8),
+            Appearance::HumanInterfaceDevice(HumanInterfaceDevice::Generic) => (15, 0),
+            Appearance::GlucoseMeter => (16, 0),
+            Appearance::RunningWalkingSensor(RunningWalkingSensor::InShoe) => (17, 1),
+            Appearance::RunningWalkingSensor(RunningWalkingSensor::OnShoe) => (17, 2),
+            Appearance::RunningWalkingSensor(RunningWalkingSensor::OnHip) => (17, 3),
+            Appearance::RunningWalkingSensor(RunningWalkingSensor::Generic) => (17, 0),
+            Appearance::Cycling(Cycling::Computer) => (18, 1),
+            Appearance::Cycling(Cycling::SpeedSensor) => (18, 2),
+            Appearance::Cycling(Cycling::CadenceSensor) => (18, 3),
+            Appearance::Cycling(Cycling::PowerSensor) => (18, 4),
+            Appearance::Cycling(Cycling::SpeedAndCadenceSensor) => (18, 5),
+            Appearance::Cycling(Cycling::Generic) => (18, 0),
+            Appearance::GenericControlDevice(GenericControlDevice::Switch) => (19, 1),
+            Appearance::GenericControlDevice(GenericControlDevice::MultiSwitch) => (19, 2),
+            Appearance::GenericControlDevice(GenericControlDevice::Button) => (19, 3),
+            Appearance::GenericControlDevice(GenericControlDevice::Slider) => (19, 4),
+            Appearance::GenericControlDevice(GenericControlDevice::Rotary) => (19, 5),
+            Appearance::GenericControlDevice(GenericControlDevice::TouchPanel) => (19, 6),
+            Appearance::GenericControlDevice(GenericControlDevice::Generic) => (19, 0),
+            Appearance::GenericNetworkDevice(GenericNetworkDevice::AccessPoint) => (20, 1),
+            Appearance::GenericNetworkDevice(GenericNetworkDevice::Generic) => (20, 0),
+            Appearance::Sensor(Sensor::Motion) => (21, 1),
+            Appearance::Sensor(Sensor::AirQuality) => (21, 2),
+            Appearance::Sensor(Sensor::Temperature) => (21, 3),
+            Appearance::Sensor(Sensor::Humidity) => (21, 4),
+            Appearance::Sensor(Sensor::Leak) => (21, 5),
+            Appearance::Sensor(Sensor::Smoke) => (21, 6),
+            Appearance::Sensor(Sensor::Occupancy) => (21, 7),
+            Appearance::Sensor(Sensor::Contact) => (21, 8),
+            Appearance::Sensor(Sensor::CarbonMonoxide) => (21, 9),
+            Appearance::Sensor(Sensor::CarbonDioxide) => (21, 10),
+            Appearance::Sensor(Sensor::AmbientLight) => (21, 11),
+            Appearance::Sensor(Sensor::Energy) => (21, 12),
+            Appearance::Sensor(Sensor::ColorLight) => (21, 13),
+            Appearance::Sensor(Sensor::Rain) => (21, 14),
+            Appearance::Sensor(Sensor::Fire) => (21, 15),
+            Appearance::Sensor(Sensor::Wind) => (21, 16),
+            Appearance::Sensor(Sensor::Proximity) => (21, 17),
+            Appearance::Sensor(Sensor::MultiSensor) => (21, 18),
+            Appearance::Sensor(Sensor::Generic) => (21, 0),
+            Appearance::LightFixture(LightFixture::WallLight) => (22, 1),
+            Appearance::LightFixture(LightFixture::CeilingLight) => (22, 2),
+            Appearance::LightFixture(LightFixture::FloorLight) => (22, 3),
+            Appearance::LightFixture(LightFixture::CabinetLight) => (22, 4),
+            Appearance::LightFixture(LightFixture::DeskLight) => (22, 5),
+            Appearance::LightFixture(LightFixture::TrofferLight) => (22, 6),
+            Appearance::LightFixture(LightFixture::PendandtLight) => (22, 7),
+            Appearance::LightFixture(LightFixture::InGroundLight) => (22, 8),
+            Appearance::LightFixture(LightFixture::FloodLight) => (22, 9),
+            Appearance::LightFixture(LightFixture::UnderwaterLight) => (22, 10),
+            Appearance::LightFixture(LightFixture::BollardLight) => (22, 11),
+            Appearance::LightFixture(LightFixture::PathwatLight) => (22, 12),
+            Appearance::LightFixture(LightFixture::GardenLight) => (22, 13),
+            Appearance::LightFixture(LightFixture::PoleTopLight) => (22, 14),
+            Appearance::LightFixture(LightFixture::Spotlight) => (22, 15),
+            Appearance::LightFixture(LightFixture::LinearLight) => (22, 16),
+            Appearance::LightFixture(LightFixture::StreetLight) => (22, 17),
+            Appearance::LightFixture(LightFixture::ShelvesLight) => (22, 18),
+            Appearance::LightFixture(LightFixture::HighBayLowBayLight) => (22, 19),
+            Appearance::LightFixture(LightFixture::EmergencyExitLight) => (22, 20),
+            Appearance::LightFixture(LightFixture::Generic) => (22, 0),
+            Appearance::Fan(Fan::Ceiling) => (23, 1),
+            Appearance::Fan(Fan::Axial) => (23, 2),
+            Appearance::Fan(Fan::Exhaust) => (23, 3),
+            Appearance::Fan(Fan::Pedestal) => (23, 4),
+            Appearance::Fan(Fan::Desk) => (23, 5),
+            Appearance::Fan(Fan::Wall) => (23, 6),
+            Appearance::Fan(Fan::Generic) => (23, 0),
+            Appearance::Reserved => {
+                return Err(Error::from(HciError::new(HciErrorKind::InvalidValue)));
+            }
+        };
+        Ok(category << 6 | sub_category)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        for value in 0..=u16::MAX {
+            let appearance = Appearance::from(value);
+            match appearance {
+                Appearance::Reserved => {
+                    assert!(u16::try_from(appearance).is_err());
+                }
+                _ => {
+                    assert_eq!(u16::try_from(appearance).unwrap(), value);
+                }
+            }
+        }
+        assert_eq!(
+            u16::try_from(Appearance::Watch(Watch::Sport)).unwrap(),
+            0x00c1
+        );
+        assert_eq!(
+            u16::try_from(Appearance::LightFixture(LightFixture::Generic)).unwrap(),
+            0x0580
+        );
+    }
+}
@@ -3,7 +3,12 @@
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct ClassOfDevice(u32);
 
-use crate::{error::HciError, error::HciErrorKind, pack::UnpackFixed, Error};
+use crate::{
+    error::HciError,
+    error::HciErrorKind,
+    pack::{PackFixed, UnpackFixed},
+    Error,
+};
 
 bitflags!(
     pub struct MajorServiceClass: u32 {
@@ -21,6 +26,17 @@ bitflags!(
     }
 );
 
+bitflags!(
+    pub struct DeviceClassImaging: u8 {
+        const RESERVED1 = 0b00_0001;
+        const RESERVED2 = 0b00_0010;
+        const DISPLAY   = 0b00_0100;
+        const CAMERA    = 0b00_1000;
+        const SCANNER   = 0b01_0000;
+        const PRINTER   = 0b10_0000;
+    }
+);
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum MajorDeviceClass {
     Miscellaneous,
@@ -118,7 +134,7 @@ pub enum DeviceClassNetwork {
 
 impl From<u8> for DeviceClassNetwork {
     fn from(value: u8) -> Self {
-        let utilization = match value & 0b11_1000 {
+        let utilization = match (value & 0b11_1000) >> 3 {
             0b000 => 100,
             0b001 => 1,
             0b010 => 17,
@@ -135,6 +151,7 @@ impl From<u8> for DeviceClassNetwork {
     }
 }
 
+#[allow(clippy::upper_case_acronyms)]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum DeviceClassAudioVideo {
     Uncategorized,
@@ -333,7 +350,7 @@ pub enum DeviceClass {
     Network(DeviceClassNetwork),
     AudioVideo(DeviceClassAudioVideo),
     Peripheral((DeviceClassKeyboardPointingDevice, DeviceClassPeripheral)),
-    Imaging,
+    Imaging(DeviceClassImaging),
     Wearable(DeviceClassWearable),
     Toy(DeviceClassToy),
     Health(DeviceClassHealth),
@@ -341,7 +358,237 @@ pub enum DeviceClass {
     Reserved(u8),
 }
 
+impl MajorDeviceClass {
+    fn value(&self) -> Result<u8, Error> {
+        let value = match self {
+            MajorDeviceClass::Miscellaneous => 0b0_0000,
+            MajorDeviceClass::Computer => 0b0_0001,
+            MajorDeviceClass::Phone => 0b0_0010,
+            MajorDeviceClass::Network => 0b0_0011,
+            MajorDeviceClass::AudioVideo => 0b0_0100,
+            MajorDeviceClass::Peripheral => 0b0_0101,
+            MajorDeviceClass::Imaging => 0b0_0110,
+            MajorDeviceClass::Wearable => 0b0_0111,
+            MajorDeviceClass::Toy => 0b0_1000,
+            MajorDeviceClass::Health => 0b0_1001,
+            MajorDeviceClass::Uncategorized => 0b1_1111,
+            MajorDeviceClass::Reserved => {
+                return Err(Error::from(HciError::new(HciErrorKind::InvalidValue)))
+            }
+        };
+        Ok(value)
+    }
+}
+
+impl DeviceClass {
+    /// Major device class this device class belongs to
+    pub fn major_device_class(&self) -> MajorDeviceClass {
+        match self {
+            DeviceClass::Miscellaneous(_) => MajorDeviceClass::Miscellaneous,
+            DeviceClass::Computer(_) => MajorDeviceClass::Computer,
+            DeviceClass::Phone(_) => MajorDeviceClass::Phone,
+            DeviceClass::Network(_) => MajorDeviceClass::Network,
+            DeviceClass::AudioVideo(_) => MajorDeviceClass::AudioVideo,
+            DeviceClass::Peripheral(_) => MajorDeviceClass::Peripheral,
+            DeviceClass::Imaging(_) => MajorDeviceClass::Imaging,
+            DeviceClass::Wearable(_) => MajorDeviceClass::Wearable,
+            DeviceClass::Toy(_) => MajorDeviceClass::Toy,
+            DeviceClass::Health(_) => MajorDeviceClass::Health,
+            DeviceClass::Uncategorized(_) => MajorDeviceClass::Uncategorized,
+            DeviceClass::Reserved(_) => MajorDeviceClass::Reserved,
+        }
+    }
+
+    /// Six bit minor device class value
+    fn minor(&self) -> Result<u8, Error> {
+        let minor = match self {
+            DeviceClass::Miscellaneous(minor)
+            | DeviceClass::Uncategorized(minor)
+            | DeviceClass::Reserved(minor) => *minor,
+            DeviceClass::Computer(minor) => match minor {
+                DeviceClassComputer::Uncategorized => 0b00_0000,
+                DeviceClassComputer::Workstation => 0b00_0001,
+                DeviceClassComputer::Server => 0b00_0010,
+                DeviceClassComputer::Laptop => 0b00_0011,
+                DeviceClassComputer::Handheld => 0b00_0100,
+                DeviceClassComputer::PalmSized => 0b00_0101,
+                DeviceClassComputer::Wearable => 0b00_0110,
+                DeviceClassComputer::Tablet => 0b00_0111,
+                DeviceClassComputer::Reserved => 0xff,
+            },
+            DeviceClass::Phone(minor) => match minor {
+                DeviceClassPhone::Uncategorized => 0b00_0000,
+                DeviceClassPhone::Cellular => 0b00_0001,
+                DeviceClassPhone::Cordless => 0b00_0010,
+                DeviceClassPhone::Smartphone => 0b00_0011,
+                DeviceClassPhone::ModemOrVoiceGateway => 0b00_0100,
+                DeviceClassPhone::Isdn => 0b00_0101,
+                DeviceClassPhone::Reserved => 0xff,
+            },
+            DeviceClass::Network(minor) => match minor {
+                DeviceClassNetwork::Uncategorized(utilization) => match utilization {
+                    100 => 0b000_000,
+                    1 => 0b001_000,
+                    17 => 0b010_000,
+                    33 => 0b011_000,
+                    50 => 0b100_000,
+                    67 => 0b101_000,
+                    99 => 0b110_000,
+                    0 => 0b111_000,
+                    _ => 0xff,
+                },
+                DeviceClassNetwork::Reserved => 0xff,
+            },
+            DeviceClass::AudioVideo(minor) => match minor {
+                DeviceClassAudioVideo::Uncategorized => 0b00_0000,
+                DeviceClassAudioVideo::Headset => 0b00_0001,
+                DeviceClassAudioVideo::HandsFree => 0b00_0010,
+                DeviceClassAudioVideo::Microphone => 0b00_0100,
+                DeviceClassAudioVideo::Loudspeaker => 0b00_0101,
+                DeviceClassAudioVideo::Headphones => 0b00_0110,
+                DeviceClassAudioVideo::PortableAudio => 0b00_0111,
+                DeviceClassAudioVideo::CarAudio => 0b00_1000,
+                DeviceClassAudioVideo::SetTopBox => 0b00_1001,
+                DeviceClassAudioVideo::HiFiAudioDevice => 0b00_1010,
+                DeviceClassAudioVideo::VCR => 0b00_1011,
+                DeviceClassAudioVideo::VideoCamera => 0b00_1100,
+                DeviceClassAudioVideo::Camcorder => 0b00_1101,
+                DeviceClassAudioVideo::VideoMonitor => 0b00_1110,
+                DeviceClassAudioVideo::VideoDisplayAndLoudspeaker => 0b00_1111,
+                DeviceClassAudioVideo::VideoConferencing => 0b01_0000,
+                DeviceClassAudioVideo::GamingToy => 0b01_0010,
+                DeviceClassAudioVideo::Reserved => 0xff,
+            },
+            DeviceClass::Peripheral((keyboard_pointing, peripheral)) => {
+                let keyboard_pointing = match keyboard_pointing {
+                    DeviceClassKeyboardPointingDevice::NotKeyboardNotPointingDevice => 0b00,
+                    DeviceClassKeyboardPointingDevice::Keyboard => 0b01,
+                    DeviceClassKeyboardPointingDevice::PointingDevice => 0b10,
+                    DeviceClassKeyboardPointingDevice::ComboDevice => 0b11,
+                };
+                let peripheral = match peripheral {
+                    DeviceClassPeripheral::Uncategorized => 0b0000,
+                    DeviceClassPeripheral::Joystick => 0b0001,
+                    DeviceClassPeripheral::Gamepad => 0b0010,
+                    DeviceClassPeripheral::RemoteControl => 0b0011,
+                    DeviceClassPeripheral::SensingDevice => 0b0100,
+                    DeviceClassPeripheral::DigitizerTablet => 0b0101,
+                    DeviceClassPeripheral::CardReader => 0b0110,
+                    DeviceClassPeripheral::DigitalPen => 0b0111,
+                    DeviceClassPeripheral::HandheldScanner => 0b1000,
+                    DeviceClassPeripheral::HandheltGesture => 0b1001,
+                    DeviceClassPeripheral::Reserved => 0xff,
+                };
+                if peripheral == 0xff {
+                    0xff
+                } else {
+                    keyboard_pointing << 4 | peripheral
+                }
+            }
+            DeviceClass::Imaging(minor) => minor.bits(),
+            DeviceClass::Wearable(minor) => match minor {
+                DeviceClassWearable::Wristwatch => 0b0001,
+                DeviceClassWearable::Pager => 0b0010,
+                DeviceClassWearable::Jacket => 0b0011,
+                DeviceClassWearable::Helmet => 0b0100,
+                DeviceClassWearable::Glasses => 0b0101,
+                DeviceClassWearable::Reserved => 0xff,
+            },
+            DeviceClass::Toy(minor) => match minor {
+                DeviceClassToy::Robot => 0b0001,
+                DeviceClassToy::Vehicle => 0b0010,
+                DeviceClassToy::Doll => 0b0011,
+                DeviceClassToy::Controller => 0b0100,
+                DeviceClassToy::Game => 0b0101,
+                DeviceClassToy::Reserved => 0xff,
+            },
+            DeviceClass::Health(minor) => match minor {
+                DeviceClassHealth::Undefined => 0b00_0000,
+                DeviceClassHealth::BloodPressureMonitor => 0b00_0001,
+                DeviceClassHealth::Thermometer => 0b00_0010,
+                DeviceClassHealth::WeighingScale => 0b00_0011,
+                DeviceClassHealth::GlucoseMeter => 0b00_0100,
+                DeviceClassHealth::PulseOximeter => 0b00_0101,
+                DeviceClassHealth::HeartRateMonitor => 0b00_0110,
+                DeviceClassHealth::HealthDataDisplay => 0b00_0111,
+                DeviceClassHealth::StepCounter => 0b00_1000,
+                DeviceClassHealth::BodyCompositionAnalyzer => 0b00_1001,
+                DeviceClassHealth::PeakFlowMonitor => 0b00_1010,
+                DeviceClassHealth::MedicationMonitor => 0b00_1011,
+                DeviceClassHealth::KneeProsthesis => 0b00_1110,
+                DeviceClassHealth::AnkleProsthesis => 0b00_1111,
+                DeviceClassHealth::GenericHealthManager => 0b01_0000,
+                DeviceClassHealth::PersonalMobilityDevice => 0b01_0001,
+                DeviceClassHealth::Reserved => 0xff,
+            },
+        };
+        if minor > 0b11_1111 {
+            return Err(Error::from(HciError::new(HciErrorKind::InvalidValue)));
+        }
+        Ok(minor)
+    }
+}
+
+/// Builder composing a class of device from its parts
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ClassOfDeviceBuilder {
+    major_device_class: MajorDeviceClass,
+    device_class: Option<DeviceClass>,
+    major_service_class: MajorServiceClass,
+}
+
+impl ClassOfDeviceBuilder {
+    /// Create a builder for the given major device class
+    pub fn new(major_device_class: MajorDeviceClass) -> ClassOfDeviceBuilder {
+        ClassOfDeviceBuilder {
+            major_device_class,
+            device_class: None,
+            major_service_class: MajorServiceClass::empty(),
+        }
+    }
+
+    /// Set the minor device class, must belong to the major device class
+    pub fn device_class(mut self, device_class: DeviceClass) -> ClassOfDeviceBuilder {
+        self.device_class = Some(device_class);
+        self
+    }
+
+    /// Set the major service classes
+    pub fn major_service_class(
+        mut self,
+        major_service_class: MajorServiceClass,
+    ) -> ClassOfDeviceBuilder {
+        self.major_service_class = major_service_class;
+        self
+    }
+
+    /// Compose the class of device
+    ///
+    /// Fails if the device class does not belong to the major device class or
+    /// if a reserved value would be encoded.
+    pub fn build(&self) -> Result<ClassOfDevice, Error> {
+        let major = self.major_device_class.value()?;
+        let minor = match self.device_class {
+            Some(ref device_class) => {
+                if device_class.major_device_class() != self.major_device_class {
+                    return Err(Error::from(HciError::new(HciErrorKind::InvalidValue)));
+                }
+                device_class.minor()?
+            }
+            None => 0,
+        };
+        Ok(ClassOfDevice(
+            self.major_service_class.bits() | u32::from(major) << 8 | u32::from(minor) << 2,
+        ))
+    }
+}
+
 impl ClassOfDevice {
+    /// Create a builder for the given major device class
+    pub fn builder(major_device_class: MajorDeviceClass) -> ClassOfDeviceBuilder {
+        ClassOfDeviceBuilder::new(major_device_class)
+    }
+
     pub fn format_code(&self) -> u8 {
         (self.0 & 0b0000_0000_0000_0000_0000_0011) as u8
     }
@@ -366,7 +613,9 @@ impl ClassOfDevice {
                 DeviceClassKeyboardPointingDevice::from(minor >> 4),
                 DeviceClassPeripheral::from(minor & 0b1111),
             )),
-            MajorDeviceClass::Imaging => DeviceClass::Imaging,
+            MajorDeviceClass::Imaging => {
+                DeviceClass::Imaging(DeviceClassImaging::from_bits_truncate(minor))
+            }
             MajorDeviceClass::Wearable => DeviceClass::Wearable(DeviceClassWearable::from(minor)),
             MajorDeviceClass::Toy => DeviceClass::Toy(DeviceClassToy::from(minor)),
             MajorDeviceClass::Health => DeviceClass::Health(DeviceClassHealth::from(minor)),
@@ -390,6 +639,18 @@ impl<'a> UnpackFixed<'a, ClassOfDevice, Error> for ClassOfDevice {
     }
 }
 
+impl PackFixed<ClassOfDevice, Error> for ClassOfDevice {
+    fn pack(&self, data: &mut [u8]) -> Result<(), Error> {
+        if data.len() != 3 {
+            return Err(Error::from(HciError::new(HciErrorKind::InvalidLength)));
+        }
+        data[0] = self.0 as u8;
+        data[1] = (self.0 >> 8) as u8;
+        data[2] = (self.0 >> 16) as u8;
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -427,4 +688,63 @@ mod tests {
                 | MajorServiceClass::OBJECT_TRANSFER
         );
     }
+
+    #[test]
+    fn build() {
+        let cod = ClassOfDevice::builder(MajorDeviceClass::Computer)
+            .device_class(DeviceClass::Computer(DeviceClassComputer::Workstation))
+            .major_service_class(MajorServiceClass::RENDERING | MajorServiceClass::OBJECT_TRANSFER)
+            .build()
+            .unwrap();
+        let mut data = [0u8; 3];
+        cod.pack(&mut data).unwrap();
+        assert_eq!(data, [0x04, 0x01, 0x14]);
+
+        let cod = ClassOfDevice::builder(MajorDeviceClass::Peripheral)
+            .device_class(DeviceClass::Peripheral((
+                DeviceClassKeyboardPointingDevice::ComboDevice,
+                DeviceClassPeripheral::CardReader,
+            )))
+            .build()
+            .unwrap();
+        assert_eq!(ClassOfDevice::unpack(&[0xd8, 0x05, 0x00]).unwrap(), cod);
+
+        let cod = ClassOfDevice::builder(MajorDeviceClass::Network)
+            .device_class(DeviceClass::Network(DeviceClassNetwork::Uncategorized(33)))
+            .build()
+            .unwrap();
+        assert_eq!(
+            cod.device_class(),
+            DeviceClass::Network(DeviceClassNetwork::Uncategorized(33))
+        );
+
+        let cod = ClassOfDevice::builder(MajorDeviceClass::Imaging)
+            .device_class(DeviceClass::Imaging(
+                DeviceClassImaging::SCANNER | DeviceClassImaging::PRINTER,
+            ))
+            .build()
+            .unwrap();
+        assert_eq!(ClassOfDevice::unpack(&[0xc0, 0x06, 0x00]).unwrap(), cod);
+        for minor in 0..=0b11_1111 {
+            let device_class = DeviceClass::Imaging(DeviceClassImaging::from_bits_truncate(minor));
+            let cod = ClassOfDevice::builder(MajorDeviceClass::Imaging)
+                .device_class(device_class.clone())
+                .build()
+                .unwrap();
+            assert_eq!(cod.device_class(), device_class);
+            assert_eq!(cod.0, 0x0600 | u32::from(minor) << 2);
+        }
+
+        assert!(ClassOfDevice::builder(MajorDeviceClass::Phone)
+            .device_class(DeviceClass::Computer(DeviceClassComputer::Laptop))
+            .build()
+            .is_err());
+        assert!(ClassOfDevice::builder(MajorDeviceClass::Toy)
+            .device_class(DeviceClass::Toy(DeviceClassToy::Reserved))
+            .build()
+            .is_err());
+        assert!(ClassOfDevice::builder(MajorDeviceClass::Reserved)
+            .build()
+            .is_err());
+    }
 }
@@ -0,0 +1,142 @@
+use std::convert::TryFrom;
+
+use byteorder::{ByteOrder, LittleEndian};
+
+use crate::{
+    eir::{DataType, EirEntry},
+    error::HciError,
+    error::HciErrorKind,
+    extended_enum,
+    pack::{PackFixed, UnpackFixed},
+    Error,
+};
+
+extended_enum!(
+    DeviceIdSource, u16,
+    Disabled => 0x0000,
+    BluetoothSig => 0x0001,
+    UsbImplementersForum => 0x0002,
+);
+
+/// Device ID profile record
+///
+/// In extended inquiry response data the record is found in entries with
+/// type 0x10, which shares its value with the security manager temporary key
+/// used in advertising data. The record is 8 octets while the key is 16.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct DeviceId {
+    /// Organisation assigning the vendor identifier
+    pub source: DeviceIdSource,
+    pub vendor: u16,
+    pub product: u16,
+    pub version: u16,
+}
+
+impl DeviceId {
+    /// Size of a packed device ID in octets
+    pub const SIZE: usize = 8;
+
+    /// Decode the record from an extended inquiry response entry
+    ///
+    /// Returns `None` for entries of another type or size, such as a security
+    /// manager temporary key, and for records with an unknown source.
+    pub fn from_eir(entry: &EirEntry) -> Option<DeviceId> {
+        if entry.data_type != DataType::SecurityManagerTemporaryKey
+            || entry.data.len() != DeviceId::SIZE
+        {
+            return None;
+        }
+        DeviceId::unpack(entry.data).ok()
+    }
+}
+
+impl PackFixed<DeviceId, Error> for DeviceId {
+    fn pack(&self, data: &mut [u8]) -> Result<(), Error> {
+        if data.len() != DeviceId::SIZE {
+            return Err(Error::from(HciError::new(HciErrorKind::InvalidLength)));
+        }
+        LittleEndian::write_u16(&mut data[0..2], u16::from(self.source));
+        LittleEndian::write_u16(&mut data[2..4], self.vendor);
+        LittleEndian::write_u16(&mut data[4..6], self.product);
+        LittleEndian::write_u16(&mut data[6..8], self.version);
+        Ok(())
+    }
+}
+
+impl<'a> UnpackFixed<'a, DeviceId, Error> for DeviceId {
+    fn unpack(data: &'a [u8]) -> Result<DeviceId, Error> {
+        if data.len() != DeviceId::SIZE {
+            return Err(Error::from(HciError::new(HciErrorKind::NotEnoughData)));
+        }
+        Ok(DeviceId {
+            source: DeviceIdSource::try_from(LittleEndian::read_u16(&data[0..2]))?,
+            vendor: LittleEndian::read_u16(&data[2..4]),
+            product: LittleEndian::read_u16(&data[4..6]),
+            version: LittleEndian::read_u16(&data[6..8]),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unpack_eir() {
+        let data = [0x09, 0x10, 0x02, 0x00, 0x6b, 0x1d, 0x46, 0x02, 0x37, 0x05];
+        let (entry, _) = EirEntry::unpack(&data).unwrap();
+        assert_eq!(entry.data_type, DataType::SecurityManagerTemporaryKey);
+        let device_id = DeviceId::unpack(entry.data).unwrap();
+        assert_eq!(
+            device_id,
+            DeviceId {
+                source: DeviceIdSource::UsbImplementersForum,
+                vendor: 0x1d6b,
+                product: 0x0246,
+                version: 0x0537,
+            }
+        );
+        let mut packed = [0u8; 8];
+        device_id.pack(&mut packed).unwrap();
+        assert_eq!(&packed, &data[2..]);
+        assert!(DeviceId::unpack(&[0x03, 0x00, 0, 0, 0, 0, 0, 0]).is_err());
+    }
+
+    #[test]
+    fn from_eir() {
+        let data = [0x02, 0x00, 0x6b, 0x1d, 0x46, 0x02, 0x37, 0x05];
+        let entry = EirEntry {
+            data_type: DataType::SecurityManagerTemporaryKey,
+            data: &data,
+        };
+        assert_eq!(
+            DeviceId::from_eir(&entry),
+            Some(DeviceId {
+                source: DeviceIdSource::UsbImplementersForum,
+                vendor: 0x1d6b,
+                product: 0x0246,
+                version: 0x0537,
+            })
+        );
+
+        let key = [0x02; 16];
+        let entry = EirEntry {
+            data_type: DataType::SecurityManagerTemporaryKey,
+            data: &key,
+        };
+        assert_eq!(DeviceId::from_eir(&entry), None);
+
+        let entry = EirEntry {
+            data_type: DataType::ClassOfDevice,
+            data: &data,
+        };
+        assert_eq!(DeviceId::from_eir(&entry), None);
+
+        let unknown = [0x03, 0x00, 0x6b, 0x1d, 0x46, 0x02, 0x37, 0x05];
+        let entry = EirEntry {
+            data_type: DataType::SecurityManagerTemporaryKey,
+            data: &unknown,
+        };
+        assert_eq!(DeviceId::from_eir(&entry), None);
+    }
+}
@@ -1,5 +1,7 @@
-mod appearance;
-mod class_of_device;
+pub mod appearance;
+pub mod class_of_device;
+pub mod device_id;
 
 pub use appearance::Appearance;
-pub use class_of_device::ClassOfDevice;
+pub use class_of_device::{ClassOfDevice, ClassOfDeviceBuilder};
+pub use device_id::{DeviceId, DeviceIdSource};
@@ -1,11 +1,7 @@
-use crate::extended_enum_other;
-/// Extended Inquiry Response (EIR)
-///
-///
-// use byteorder::{ByteOrder, LittleEndian};
+//! Extended Inquiry Response (EIR)
 
 // Reference, https://www.bluetooth.com/specifications/assigned-numbers/generic-access-profile/
-use crate::{error::HciError, error::HciErrorKind, Error};
+use crate::{error::HciError, error::HciErrorKind, extended_enum_other, Error};
 
 extended_enum_other!(DataType, u8,
     Flags => 0x01,
@@ -54,6 +50,7 @@ extended_enum_other!(DataType, u8,
     ManufacturerData => 0xff,
 );
 
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub struct EirEntry<'a> {
     pub data_type: DataType,
     pub data: &'a [u8],
@@ -72,8 +69,21 @@ impl<'a> EirEntry<'a> {
         let data = if length > 1 { &data[2..=length] } else { &[] };
         Ok((EirEntry { data_type, data }, length + 1))
     }
+
+    /// Unpack all entries, stopping at the first zero length entry
+    pub fn unpack_all(data: &'a [u8]) -> Result<Vec<EirEntry<'a>>, Error> {
+        let mut entries = Vec::new();
+        let mut offset = 0;
+        while offset < data.len() && data[offset] != 0 {
+            let (entry, used) = EirEntry::unpack(&data[offset..])?;
+            entries.push(entry);
+            offset += used;
+        }
+        Ok(entries)
+    }
 }
 
+#[allow(dead_code)]
 pub struct Flags {
     le_limited_discoverable_mode: bool,
     le_general_discoverable_mode: bool,
@@ -5,12 +5,15 @@ use std::result;
 use std::str;
 use std::string;
 
+use crate::Status;
+
 #[derive(Debug)]
 pub enum HciErrorKind {
     NotEnoughData,
     NotFound,
     InvalidValue,
     InvalidLength,
+    InvalidAddress,
 }
 
 #[derive(Debug)]
@@ -42,6 +45,8 @@ pub enum Error {
     Utf8(str::Utf8Error),
     FromUtf8(string::FromUtf8Error),
     Hci(HciError),
+    /// Command completed with a status other than success
+    Status(Status),
 }
 
 impl fmt::Display for Error {
@@ -51,6 +56,7 @@ impl fmt::Display for Error {
             Error::Utf8(ref err) => write!(f, "UTF8 error: {}", err),
             Error::FromUtf8(ref err) => write!(f, "From UTF8 error: {}", err),
             Error::Hci(ref err) => write!(f, "HCI error: {}", err),
+            Error::Status(ref status) => write!(f, "Command failed: {:?}", status),
         }
     }
 }
@@ -62,6 +68,7 @@ impl error::Error for Error {
             Error::Utf8(ref err) => Some(err),
             Error::FromUtf8(ref err) => Some(err),
             Error::Hci(ref err) => Some(err),
+            Error::Status(_) => None,
         }
     }
 }
@@ -78,6 +85,12 @@ impl From<HciError> for Error {
     }
 }
 
+impl From<Status> for Error {
+    fn from(status: Status) -> Error {
+        Error::Status(status)
+    }
+}
+
 impl From<str::Utf8Error> for Error {
     fn from(err: str::Utf8Error) -> Error {
         Error::Utf8(err)
@@ -13,6 +13,17 @@ pub struct CommandComplete<'a> {
     pub data: &'a [u8],
 }
 
+impl<'a> CommandComplete<'a> {
+    /// Get the reply parameters of a successful command, or the status as
+    /// error if the command failed
+    pub fn parameters(&self) -> Result<&'a [u8], Error> {
+        match self.status {
+            Status::Success => Ok(self.data),
+            status => Err(Error::from(status)),
+        }
+    }
+}
+
 impl<'a> Unpack<'a, CommandComplete<'a>, Error> for CommandComplete<'a> {
     fn unpack(data: &'a [u8]) -> Result<(CommandComplete<'a>, usize), Error> {
         if data.len() < 3 {
@@ -0,0 +1,29 @@
+use crate::{
+    error::HciError, error::HciErrorKind, operations::DeviceAction, pack::UnpackFixed, AddressInfo,
+    Error,
+};
+
+/// A device was added to the list of devices the kernel connects to or
+/// accepts connections from
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct DeviceAdded {
+    pub address_info: AddressInfo,
+    pub action: DeviceAction,
+}
+
+impl DeviceAdded {
+    /// Size of the packed event in octets
+    pub const SIZE: usize = AddressInfo::SIZE + 1;
+}
+
+impl<'a> UnpackFixed<'a, DeviceAdded, Error> for DeviceAdded {
+    fn unpack(data: &'a [u8]) -> Result<DeviceAdded, Error> {
+        if data.len() != DeviceAdded::SIZE {
+            return Err(Error::from(HciError::new(HciErrorKind::NotEnoughData)));
+        }
+        Ok(DeviceAdded {
+            address_info: AddressInfo::unpack(&data[..AddressInfo::SIZE])?,
+            action: DeviceAction::from(data[AddressInfo::SIZE]),
+        })
+    }
+}
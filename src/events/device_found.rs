@@ -1,6 +1,23 @@
 use byteorder::{ByteOrder, LittleEndian};
 
-use crate::{error::HciError, error::HciErrorKind, pack::UnpackFixed, AddressInfo, Error};
+use crate::{
+    eir::{DataType, EirEntry},
+    error::HciError,
+    error::HciErrorKind,
+    pack::UnpackFixed,
+    AddressInfo, Error,
+};
+
+bitflags!(
+    pub struct DeviceFoundFlags: u32 {
+        const CONFIRM_NAME = 0x0000_0001;
+        const LEGACY_PAIRING = 0x0000_0002;
+        const NOT_CONNECTABLE = 0x0000_0004;
+        const INITIATED_CONNECTION = 0x0000_0008;
+        const NAME_REQUEST_FAILED = 0x0000_0010;
+        const SCAN_RESPONSE = 0x0000_0020;
+    }
+);
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub struct DeviceFound<'a> {
@@ -37,4 +54,17 @@ impl<'a> DeviceFound<'a> {
             end,
         ))
     }
+
+    /// Flags known to this crate, use the `flags` field for all flags
+    pub fn flags(&self) -> DeviceFoundFlags {
+        DeviceFoundFlags::from_bits_truncate(self.flags)
+    }
+
+    /// Check if the EIR data holds the complete local name of the device
+    pub fn has_complete_name(&self) -> Result<bool, Error> {
+        let entries = EirEntry::unpack_all(self.data)?;
+        Ok(entries
+            .iter()
+            .any(|entry| entry.data_type == DataType::CompleteLocalName))
+    }
 }
@@ -1,19 +1,24 @@
 mod command;
+mod device_added;
 mod device_found;
 mod discovering;
+mod new_connection_parameters;
 
 use byteorder::{ByteOrder, LittleEndian};
 
 use crate::{
     error::{HciError, HciErrorKind},
     extended_enum_other,
+    operations::{ConfigurationOptions, LocalName},
     pack::{Unpack, UnpackFixed},
-    ClassOfDevice, Error,
+    AddressInfo, ClassOfDevice, Error,
 };
 
 pub use command::{CommandComplete, CommandStatus};
-pub use device_found::DeviceFound;
+pub use device_added::DeviceAdded;
+pub use device_found::{DeviceFound, DeviceFoundFlags};
 pub use discovering::{Discovering, DiscoveringType};
+pub use new_connection_parameters::NewConnectionParameters;
 
 use bitflags;
 
@@ -39,6 +44,16 @@ bitflags!(
     }
 );
 
+impl<'a> UnpackFixed<'a, Settings, Error> for Settings {
+    /// Unpack settings, ignoring settings unknown to this crate
+    fn unpack(data: &'a [u8]) -> Result<Settings, Error> {
+        if data.len() != 4 {
+            return Err(Error::from(HciError::new(HciErrorKind::NotEnoughData)));
+        }
+        Ok(Settings::from_bits_truncate(LittleEndian::read_u32(data)))
+    }
+}
+
 extended_enum_other!(EventId, u16,
     CommandComplete => 0x0001,
     CommandStatus => 0x0002,
@@ -89,8 +104,17 @@ pub enum Event<'a> {
     IndexRemoved,
     ClassOfDeviceChanged(ClassOfDevice),
     NewSettings(Settings),
+    LocalNameChanged(LocalName),
     DeviceFound(DeviceFound<'a>),
     Discovering(Discovering),
+    DeviceBlocked(AddressInfo),
+    DeviceUnblocked(AddressInfo),
+    NewConfigurationOptions(ConfigurationOptions),
+    DeviceAdded(DeviceAdded),
+    DeviceRemoved(AddressInfo),
+    NewConnectionParameters(NewConnectionParameters),
+    AdvertisingAdded(u8),
+    AdvertisingRemoved(u8),
     Other((EventId, &'a [u8])),
 }
 
@@ -124,13 +148,16 @@ impl<'a> Event<'a> {
                 if data.len() < 4 {
                     return Err(Error::from(HciError::new(HciErrorKind::NotEnoughData)));
                 }
-                let settings = LittleEndian::read_u32(&data[0..4]);
-                let settings = match Settings::from_bits(settings) {
-                    Some(v) => v,
-                    None => return Err(Error::from(HciError::new(HciErrorKind::InvalidValue))),
-                };
+                let settings = Settings::unpack(&data[0..4])?;
                 Ok((Event::NewSettings(settings), 4))
             }
+            EventId::LocalNameChanged => {
+                if data.len() < LocalName::SIZE {
+                    return Err(Error::from(HciError::new(HciErrorKind::NotEnoughData)));
+                }
+                let local_name = LocalName::unpack(&data[..LocalName::SIZE])?;
+                Ok((Event::LocalNameChanged(local_name), LocalName::SIZE))
+            }
             EventId::DeviceFound => {
                 let (event, used) = DeviceFound::unpack(data)?;
                 Ok((Event::DeviceFound(event), used))
@@ -139,7 +166,204 @@ impl<'a> Event<'a> {
                 let event = Discovering::unpack(&data[..2])?;
                 Ok((Event::Discovering(event), 2))
             }
-            _ => Ok((Event::Other((event_id, &data)), data.len())),
+            EventId::DeviceBlocked => {
+                if data.len() < AddressInfo::SIZE {
+                    return Err(Error::from(HciError::new(HciErrorKind::NotEnoughData)));
+                }
+                let address_info = AddressInfo::unpack(&data[..AddressInfo::SIZE])?;
+                Ok((Event::DeviceBlocked(address_info), AddressInfo::SIZE))
+            }
+            EventId::DeviceUnblocked => {
+                if data.len() < AddressInfo::SIZE {
+                    return Err(Error::from(HciError::new(HciErrorKind::NotEnoughData)));
+                }
+                let address_info = AddressInfo::unpack(&data[..AddressInfo::SIZE])?;
+                Ok((Event::DeviceUnblocked(address_info), AddressInfo::SIZE))
+            }
+            EventId::NewConfigurationOptions => {
+                if data.len() < 4 {
+                    return Err(Error::from(HciError::new(HciErrorKind::NotEnoughData)));
+                }
+                let options = ConfigurationOptions::unpack(&data[..4])?;
+                Ok((Event::NewConfigurationOptions(options), 4))
+            }
+            EventId::DeviceAdded => {
+                if data.len() < DeviceAdded::SIZE {
+                    return Err(Error::from(HciError::new(HciErrorKind::NotEnoughData)));
+                }
+                let device_added = DeviceAdded::unpack(&data[..DeviceAdded::SIZE])?;
+                Ok((Event::DeviceAdded(device_added), DeviceAdded::SIZE))
+            }
+            EventId::DeviceRemoved => {
+                if data.len() < AddressInfo::SIZE {
+                    return Err(Error::from(HciError::new(HciErrorKind::NotEnoughData)));
+                }
+                let address_info = AddressInfo::unpack(&data[..AddressInfo::SIZE])?;
+                Ok((Event::DeviceRemoved(address_info), AddressInfo::SIZE))
+            }
+            EventId::NewConnectionParameters => {
+                if data.len() < NewConnectionParameters::SIZE {
+                    return Err(Error::from(HciError::new(HciErrorKind::NotEnoughData)));
+                }
+                let event =
+                    NewConnectionParameters::unpack(&data[..NewConnectionParameters::SIZE])?;
+                Ok((
+                    Event::NewConnectionParameters(event),
+                    NewConnectionParameters::SIZE,
+                ))
+            }
+            EventId::AdvertisingAdded => {
+                if data.is_empty() {
+                    return Err(Error::from(HciError::new(HciErrorKind::NotEnoughData)));
+                }
+                Ok((Event::AdvertisingAdded(data[0]), 1))
+            }
+            EventId::AdvertisingRemoved => {
+                if data.is_empty() {
+                    return Err(Error::from(HciError::new(HciErrorKind::NotEnoughData)));
+                }
+                Ok((Event::AdvertisingRemoved(data[0]), 1))
+            }
+            _ => Ok((Event::Other((event_id, data)), data.len())),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::{
+        operations::{ConnectionParameters, DeviceAction},
+        AddressType, HardwareAddress,
+    };
+
+    #[test]
+    fn local_name_changed() {
+        let mut data = [0u8; LocalName::SIZE];
+        data[..5].copy_from_slice(b"Kiosk");
+        data[249..254].copy_from_slice(b"Kiosk");
+        let (event, used) = Event::unpack(EventId::LocalNameChanged, &data).unwrap();
+        assert_eq!(used, LocalName::SIZE);
+        assert_eq!(
+            event,
+            Event::LocalNameChanged(LocalName {
+                name: "Kiosk".to_string(),
+                short_name: "Kiosk".to_string(),
+            })
+        );
+        assert!(Event::unpack(EventId::LocalNameChanged, &data[..259]).is_err());
+    }
+
+    #[test]
+    fn device_blocked_unblocked() {
+        let data = [0x01, 0x02, 0x03, 0x04, 0x05, 0xc6, 0x02, 0xff];
+        let address_info = AddressInfo {
+            address: HardwareAddress::from([0x01, 0x02, 0x03, 0x04, 0x05, 0xc6]),
+            address_type: AddressType::LeRandom,
+        };
+        let (event, used) = Event::unpack(EventId::DeviceBlocked, &data).unwrap();
+        assert_eq!(used, AddressInfo::SIZE);
+        assert_eq!(event, Event::DeviceBlocked(address_info));
+        let (event, used) = Event::unpack(EventId::DeviceUnblocked, &data).unwrap();
+        assert_eq!(used, AddressInfo::SIZE);
+        assert_eq!(event, Event::DeviceUnblocked(address_info));
+        assert!(Event::unpack(EventId::DeviceBlocked, &data[..6]).is_err());
+        assert!(Event::unpack(EventId::DeviceUnblocked, &data[..6]).is_err());
+    }
+
+    #[test]
+    fn new_configuration_options() {
+        let data = [0x06, 0x00, 0x00, 0x00];
+        let (event, used) = Event::unpack(EventId::NewConfigurationOptions, &data).unwrap();
+        assert_eq!(used, 4);
+        assert_eq!(
+            event,
+            Event::NewConfigurationOptions(ConfigurationOptions::PUBLIC_ADDRESS)
+        );
+        assert!(Event::unpack(EventId::NewConfigurationOptions, &data[..3]).is_err());
+    }
+
+    #[test]
+    fn device_added_removed() {
+        let data = [0x01, 0x02, 0x03, 0x04, 0x05, 0xc6, 0x02, 0x02];
+        let address_info = AddressInfo {
+            address: HardwareAddress::from([0x01, 0x02, 0x03, 0x04, 0x05, 0xc6]),
+            address_type: AddressType::LeRandom,
+        };
+        let (event, used) = Event::unpack(EventId::DeviceAdded, &data).unwrap();
+        assert_eq!(used, DeviceAdded::SIZE);
+        assert_eq!(
+            event,
+            Event::DeviceAdded(DeviceAdded {
+                address_info,
+                action: DeviceAction::AutoConnect,
+            })
+        );
+        assert!(Event::unpack(EventId::DeviceAdded, &data[..7]).is_err());
+        let (event, _) = Event::unpack(
+            EventId::DeviceAdded,
+            &[0x01, 0x02, 0x03, 0x04, 0x05, 0xc6, 0x02, 0x03],
+        )
+        .unwrap();
+        assert_eq!(
+            event,
+            Event::DeviceAdded(DeviceAdded {
+                address_info,
+                action: DeviceAction::Other(0x03),
+            })
+        );
+
+        let (event, used) = Event::unpack(EventId::DeviceRemoved, &data[..7]).unwrap();
+        assert_eq!(used, AddressInfo::SIZE);
+        assert_eq!(event, Event::DeviceRemoved(address_info));
+        assert!(Event::unpack(EventId::DeviceRemoved, &data[..6]).is_err());
+    }
+
+    #[test]
+    fn new_connection_parameters() {
+        // The timeout equals (1 + latency) * max interval * 2, which the
+        // kernel accepts
+        let data = [
+            0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x01, 0x01, 0x28, 0x00, 0x28, 0x00, 0x04, 0x00,
+            0x32, 0x00, 0xff,
+        ];
+        let (event, used) = Event::unpack(EventId::NewConnectionParameters, &data).unwrap();
+        assert_eq!(used, NewConnectionParameters::SIZE);
+        let event = match event {
+            Event::NewConnectionParameters(event) => event,
+            event => panic!("Unexpected event {:?}", event),
+        };
+        assert!(event.store_hint);
+        let parameters = event.parameters;
+        assert_eq!(
+            parameters.address_info(),
+            AddressInfo {
+                address: HardwareAddress::from([0x01, 0x02, 0x03, 0x04, 0x05, 0x06]),
+                address_type: AddressType::LePublic,
+            }
+        );
+        assert_eq!(parameters.min_interval(), Duration::from_millis(50));
+        assert_eq!(parameters.max_interval(), Duration::from_millis(50));
+        assert_eq!(parameters.latency(), 4);
+        assert_eq!(parameters.timeout(), Duration::from_millis(500));
+        assert!(
+            ConnectionParameters::from_units(parameters.address_info(), 40, 40, 4, 50).is_err()
+        );
+
+        assert!(Event::unpack(EventId::NewConnectionParameters, &data[..15]).is_err());
+    }
+
+    #[test]
+    fn advertising_added_removed() {
+        let (event, used) = Event::unpack(EventId::AdvertisingAdded, &[0x03]).unwrap();
+        assert_eq!(used, 1);
+        assert_eq!(event, Event::AdvertisingAdded(3));
+        let (event, used) = Event::unpack(EventId::AdvertisingRemoved, &[0x03]).unwrap();
+        assert_eq!(used, 1);
+        assert_eq!(event, Event::AdvertisingRemoved(3));
+        assert!(Event::unpack(EventId::AdvertisingAdded, &[]).is_err());
+        assert!(Event::unpack(EventId::AdvertisingRemoved, &[]).is_err());
+    }
+}
@@ -0,0 +1,40 @@
+use crate::{
+    error::HciError, error::HciErrorKind, operations::ConnectionParameters, pack::UnpackFixed,
+    AddressInfo, Error,
+};
+
+/// A device requested new LE connection parameters
+///
+/// If `store_hint` is set the parameters should be stored and loaded with
+/// LoadConnectionParameters on the next start.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct NewConnectionParameters {
+    pub store_hint: bool,
+    pub parameters: ConnectionParameters,
+}
+
+impl NewConnectionParameters {
+    /// Size of the packed event in octets
+    pub const SIZE: usize = ConnectionParameters::SIZE + 1;
+}
+
+impl<'a> UnpackFixed<'a, NewConnectionParameters, Error> for NewConnectionParameters {
+    fn unpack(data: &'a [u8]) -> Result<NewConnectionParameters, Error> {
+        if data.len() != NewConnectionParameters::SIZE {
+            return Err(Error::from(HciError::new(HciErrorKind::NotEnoughData)));
+        }
+        let store_hint = match data[AddressInfo::SIZE] {
+            0x00 => false,
+            0x01 => true,
+            _ => return Err(Error::from(HciError::new(HciErrorKind::InvalidValue))),
+        };
+        // The store hint is placed between the address and the parameters
+        let mut parameters = [0u8; ConnectionParameters::SIZE];
+        parameters[..AddressInfo::SIZE].copy_from_slice(&data[..AddressInfo::SIZE]);
+        parameters[AddressInfo::SIZE..].copy_from_slice(&data[AddressInfo::SIZE + 1..]);
+        Ok(NewConnectionParameters {
+            store_hint,
+            parameters: ConnectionParameters::unpack(&parameters)?,
+        })
+    }
+}
@@ -1,5 +1,7 @@
 use std::fmt;
 
+use crate::{system, Error};
+
 /// HardwareAddress is an six octet identifier
 ///
 /// HardwareAddress could be used for MAC address for Ethernet (802.3),
@@ -18,6 +20,33 @@ impl HardwareAddress {
     pub fn as_ptr(self) -> *const u8 {
         self.value.as_ptr()
     }
+
+    /// Generate a static random address using the random number generator of
+    /// the operating system
+    pub fn random_static() -> Result<HardwareAddress, Error> {
+        loop {
+            let mut value = [0u8; 6];
+            system::random_bytes(&mut value)?;
+            value[5] |= 0xc0;
+            let address = HardwareAddress { value };
+            if address.is_static_random() {
+                return Ok(address);
+            }
+        }
+    }
+
+    /// Check if this is a valid static random address
+    ///
+    /// The two most significant bits must be set, and the remaining bits must
+    /// neither be all zeros nor all ones.
+    pub fn is_static_random(self) -> bool {
+        if self.value[5] & 0xc0 != 0xc0 {
+            return false;
+        }
+        let zeros = self.value[..5].iter().all(|b| *b == 0x00) && self.value[5] == 0xc0;
+        let ones = self.value.iter().all(|b| *b == 0xff);
+        !zeros && !ones
+    }
 }
 
 impl fmt::Display for HardwareAddress {
@@ -50,3 +79,22 @@ impl From<[u8; 6]> for HardwareAddress {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn static_random() {
+        for _ in 0..32 {
+            let address = HardwareAddress::random_static().unwrap();
+            assert!(address.is_static_random());
+            assert_eq!(address.bytes()[5] & 0xc0, 0xc0);
+        }
+        assert!(HardwareAddress::from([0x01, 0x02, 0x03, 0x04, 0x05, 0xc6]).is_static_random());
+        assert!(!HardwareAddress::from([0x01, 0x02, 0x03, 0x04, 0x05, 0x46]).is_static_random());
+        assert!(!HardwareAddress::from([0x00, 0x00, 0x00, 0x00, 0x00, 0xc0]).is_static_random());
+        assert!(!HardwareAddress::from([0xff; 6]).is_static_random());
+        assert!(HardwareAddress::from([0xff, 0xff, 0xff, 0xff, 0xff, 0xfe]).is_static_random());
+    }
+}
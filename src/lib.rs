@@ -2,28 +2,30 @@
 extern crate bitflags;
 
 mod address_info;
-mod common;
+pub mod common;
 pub mod eir;
 pub mod error;
 pub mod events;
 #[macro_use]
 mod extended_enum;
 mod hardware_address;
-mod operations;
+pub mod operations;
 pub mod pack;
 mod socket;
 mod status;
 mod system;
+mod uuid;
 
 pub use system::{
     HCI_CHANNEL_CONTROL, HCI_CHANNEL_LOGGING, HCI_CHANNEL_MONITOR, HCI_CHANNEL_RAW,
     HCI_CHANNEL_USER,
 };
 
-pub use address_info::AddressInfo;
+pub use address_info::{AddressInfo, AddressType, AddressTypes};
 pub use common::{Appearance, ClassOfDevice};
 pub use error::Error;
 pub use hardware_address::HardwareAddress;
 pub use operations::OperationId;
 pub use socket::Socket;
 pub use status::Status;
+pub use uuid::Uuid;
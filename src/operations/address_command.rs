@@ -0,0 +1,44 @@
+/// Creates a command with the address of a device as the only parameter.
+/// The reply of the command holds the same address.
+macro_rules! address_command {
+    ( $(#[$meta:meta])* $name:ident ) => (
+        $(#[$meta])*
+        #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+        pub struct $name {
+            pub address_info: $crate::AddressInfo,
+        }
+
+        impl $name {
+            /// Create a new command
+            pub fn new(address_info: $crate::AddressInfo) -> $name {
+                $name { address_info }
+            }
+        }
+
+        impl $crate::pack::Pack<$name, $crate::Error> for $name {
+            fn pack(&self, data: &mut [u8]) -> Result<usize, $crate::Error> {
+                use $crate::pack::PackFixed;
+                if data.len() < $crate::AddressInfo::SIZE {
+                    return Err($crate::Error::from($crate::error::HciError::new(
+                        $crate::error::HciErrorKind::InvalidLength,
+                    )));
+                }
+                self.address_info.pack(&mut data[..$crate::AddressInfo::SIZE])?;
+                Ok($crate::AddressInfo::SIZE)
+            }
+        }
+
+        impl<'a> $crate::pack::UnpackFixed<'a, $crate::AddressInfo, $crate::Error> for $name {
+            fn unpack(data: &'a [u8]) -> Result<$crate::AddressInfo, $crate::Error> {
+                <$crate::AddressInfo as $crate::pack::UnpackFixed<
+                    $crate::AddressInfo,
+                    $crate::Error,
+                >>::unpack(data)
+            }
+        }
+
+        impl $crate::operations::Command for $name {
+            const OPERATION: $crate::OperationId = $crate::OperationId::$name;
+        }
+    );
+}
@@ -0,0 +1,379 @@
+use std::time::Duration;
+
+use byteorder::{ByteOrder, LittleEndian};
+
+use crate::{
+    error::HciError,
+    error::HciErrorKind,
+    pack::{Pack, UnpackFixed},
+    Error, OperationId,
+};
+
+use super::Command;
+
+use bitflags;
+
+bitflags!(
+    pub struct AdvertisingFlags: u32 {
+        const CONNECTABLE = 0x0000_0001;
+        const DISCOVERABLE = 0x0000_0002;
+        const LIMITED_DISCOVERABLE = 0x0000_0004;
+        const MANAGED_FLAGS = 0x0000_0008;
+        const TX_POWER = 0x0000_0010;
+        const APPEARANCE = 0x0000_0020;
+        const LOCAL_NAME = 0x0000_0040;
+        const SECONDARY_1M = 0x0000_0080;
+        const SECONDARY_2M = 0x0000_0100;
+        const SECONDARY_CODED = 0x0000_0200;
+        const CAN_SET_TX_POWER = 0x0000_0400;
+        const HARDWARE_OFFLOAD = 0x0000_0800;
+        const PARAMETER_DURATION = 0x0000_1000;
+        const PARAMETER_TIMEOUT = 0x0000_2000;
+        const PARAMETER_INTERVALS = 0x0000_4000;
+        const PARAMETER_TX_POWER = 0x0000_8000;
+        const SCAN_RESPONSE = 0x0001_0000;
+    }
+);
+
+impl<'a> UnpackFixed<'a, AdvertisingFlags, Error> for AdvertisingFlags {
+    /// Unpack advertising flags, ignoring flags unknown to this crate
+    fn unpack(data: &'a [u8]) -> Result<AdvertisingFlags, Error> {
+        if data.len() != 4 {
+            return Err(Error::from(HciError::new(HciErrorKind::NotEnoughData)));
+        }
+        Ok(AdvertisingFlags::from_bits_truncate(
+            LittleEndian::read_u32(data),
+        ))
+    }
+}
+
+/// Convert a duration to whole seconds, rounding up so that a non-zero
+/// duration does not become zero, fails if it does not fit
+fn to_seconds(duration: Duration) -> Result<u16, Error> {
+    let mut seconds = duration.as_secs();
+    if duration.subsec_nanos() > 0 {
+        seconds += 1;
+    }
+    if seconds > u64::from(u16::MAX) {
+        return Err(Error::from(HciError::new(HciErrorKind::InvalidValue)));
+    }
+    Ok(seconds as u16)
+}
+
+/// Add or replace an advertising instance
+///
+/// The advertising data and scan response are raw EIR data, the kernel adds
+/// the fields selected by the flags, for example the local name, which
+/// reduces the space available. The duration is the time the instance is
+/// advertised before the kernel rotates to the next instance, the timeout is
+/// the time after which the instance is removed. Both are rounded up to whole
+/// seconds, zero selects the default duration and no timeout. The reply
+/// holds the instance.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AddAdvertising {
+    pub instance: u8,
+    pub flags: AdvertisingFlags,
+    pub duration: Duration,
+    pub timeout: Duration,
+    pub advertising_data: Vec<u8>,
+    pub scan_response: Vec<u8>,
+}
+
+impl AddAdvertising {
+    /// Create a new command with the default duration and no timeout
+    pub fn new(
+        instance: u8,
+        flags: AdvertisingFlags,
+        advertising_data: Vec<u8>,
+        scan_response: Vec<u8>,
+    ) -> AddAdvertising {
+        AddAdvertising {
+            instance,
+            flags,
+            duration: Duration::from_secs(0),
+            timeout: Duration::from_secs(0),
+            advertising_data,
+            scan_response,
+        }
+    }
+}
+
+impl Pack<AddAdvertising, Error> for AddAdvertising {
+    fn pack(&self, data: &mut [u8]) -> Result<usize, Error> {
+        if self.instance == 0 {
+            return Err(Error::from(HciError::new(HciErrorKind::InvalidValue)));
+        }
+        let advertising_length = self.advertising_data.len();
+        let scan_response_length = self.scan_response.len();
+        if advertising_length > usize::from(u8::MAX) || scan_response_length > usize::from(u8::MAX)
+        {
+            return Err(Error::from(HciError::new(HciErrorKind::InvalidLength)));
+        }
+        let size = 11 + advertising_length + scan_response_length;
+        if data.len() < size {
+            return Err(Error::from(HciError::new(HciErrorKind::InvalidLength)));
+        }
+        data[0] = self.instance;
+        LittleEndian::write_u32(&mut data[1..5], self.flags.bits());
+        LittleEndian::write_u16(&mut data[5..7], to_seconds(self.duration)?);
+        LittleEndian::write_u16(&mut data[7..9], to_seconds(self.timeout)?);
+        data[9] = advertising_length as u8;
+        data[10] = scan_response_length as u8;
+        data[11..11 + advertising_length].copy_from_slice(&self.advertising_data);
+        data[11 + advertising_length..size].copy_from_slice(&self.scan_response);
+        Ok(size)
+    }
+}
+
+impl<'a> UnpackFixed<'a, u8, Error> for AddAdvertising {
+    fn unpack(data: &'a [u8]) -> Result<u8, Error> {
+        if data.len() != 1 {
+            return Err(Error::from(HciError::new(HciErrorKind::NotEnoughData)));
+        }
+        Ok(data[0])
+    }
+}
+
+impl Command for AddAdvertising {
+    const OPERATION: OperationId = OperationId::AddAdvertising;
+}
+
+/// Remove an advertising instance
+///
+/// The reply holds the instance.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct RemoveAdvertising {
+    pub instance: u8,
+}
+
+impl RemoveAdvertising {
+    /// Create a new command
+    pub fn new(instance: u8) -> RemoveAdvertising {
+        RemoveAdvertising { instance }
+    }
+
+    /// Create a command removing all instances
+    pub fn all() -> RemoveAdvertising {
+        RemoveAdvertising { instance: 0 }
+    }
+}
+
+impl Pack<RemoveAdvertising, Error> for RemoveAdvertising {
+    fn pack(&self, data: &mut [u8]) -> Result<usize, Error> {
+        if data.is_empty() {
+            return Err(Error::from(HciError::new(HciErrorKind::InvalidLength)));
+        }
+        data[0] = self.instance;
+        Ok(1)
+    }
+}
+
+impl<'a> UnpackFixed<'a, u8, Error> for RemoveAdvertising {
+    fn unpack(data: &'a [u8]) -> Result<u8, Error> {
+        AddAdvertising::unpack(data)
+    }
+}
+
+impl Command for RemoveAdvertising {
+    const OPERATION: OperationId = OperationId::RemoveAdvertising;
+}
+
+/// Read the advertising features supported by a controller
+///
+/// The reply is an `AdvertisingFeatures`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ReadAdvancedFeatures;
+
+impl Pack<ReadAdvancedFeatures, Error> for ReadAdvancedFeatures {
+    fn pack(&self, _data: &mut [u8]) -> Result<usize, Error> {
+        Ok(0)
+    }
+}
+
+impl<'a> UnpackFixed<'a, AdvertisingFeatures, Error> for ReadAdvancedFeatures {
+    fn unpack(data: &'a [u8]) -> Result<AdvertisingFeatures, Error> {
+        AdvertisingFeatures::unpack(data)
+    }
+}
+
+impl Command for ReadAdvancedFeatures {
+    const OPERATION: OperationId = OperationId::ReadAdvancedFeatures;
+}
+
+/// Advertising features of a controller
+///
+/// The data lengths are the maximum without any flags adding fields, use
+/// GetAdvertisingSizeInformation for the space left with a set of flags.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AdvertisingFeatures {
+    pub supported_flags: AdvertisingFlags,
+    pub max_advertising_data_length: u8,
+    pub max_scan_response_length: u8,
+    pub max_instances: u8,
+    /// Instances currently in use
+    pub instances: Vec<u8>,
+}
+
+impl<'a> UnpackFixed<'a, AdvertisingFeatures, Error> for AdvertisingFeatures {
+    fn unpack(data: &'a [u8]) -> Result<AdvertisingFeatures, Error> {
+        if data.len() < 8 {
+            return Err(Error::from(HciError::new(HciErrorKind::NotEnoughData)));
+        }
+        let count = usize::from(data[7]);
+        if data.len() != 8 + count {
+            return Err(Error::from(HciError::new(HciErrorKind::InvalidLength)));
+        }
+        Ok(AdvertisingFeatures {
+            supported_flags: AdvertisingFlags::unpack(&data[0..4])?,
+            max_advertising_data_length: data[4],
+            max_scan_response_length: data[5],
+            max_instances: data[6],
+            instances: data[8..].to_vec(),
+        })
+    }
+}
+
+/// Get the space available for advertising data and scan response of an
+/// instance with the given flags
+///
+/// The reply is an `AdvertisingSizeInformation`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct GetAdvertisingSizeInformation {
+    pub instance: u8,
+    pub flags: AdvertisingFlags,
+}
+
+impl GetAdvertisingSizeInformation {
+    /// Create a new command
+    pub fn new(instance: u8, flags: AdvertisingFlags) -> GetAdvertisingSizeInformation {
+        GetAdvertisingSizeInformation { instance, flags }
+    }
+}
+
+impl Pack<GetAdvertisingSizeInformation, Error> for GetAdvertisingSizeInformation {
+    fn pack(&self, data: &mut [u8]) -> Result<usize, Error> {
+        if data.len() < 5 {
+            return Err(Error::from(HciError::new(HciErrorKind::InvalidLength)));
+        }
+        data[0] = self.instance;
+        LittleEndian::write_u32(&mut data[1..5], self.flags.bits());
+        Ok(5)
+    }
+}
+
+impl<'a> UnpackFixed<'a, AdvertisingSizeInformation, Error> for GetAdvertisingSizeInformation {
+    fn unpack(data: &'a [u8]) -> Result<AdvertisingSizeInformation, Error> {
+        AdvertisingSizeInformation::unpack(data)
+    }
+}
+
+impl Command for GetAdvertisingSizeInformation {
+    const OPERATION: OperationId = OperationId::GetAdvertisingSizeInformation;
+}
+
+/// Space available for advertising data and scan response of an instance
+/// with a set of flags
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct AdvertisingSizeInformation {
+    pub instance: u8,
+    pub flags: AdvertisingFlags,
+    pub max_advertising_data_length: u8,
+    pub max_scan_response_length: u8,
+}
+
+impl<'a> UnpackFixed<'a, AdvertisingSizeInformation, Error> for AdvertisingSizeInformation {
+    fn unpack(data: &'a [u8]) -> Result<AdvertisingSizeInformation, Error> {
+        if data.len() != 7 {
+            return Err(Error::from(HciError::new(HciErrorKind::NotEnoughData)));
+        }
+        Ok(AdvertisingSizeInformation {
+            instance: data[0],
+            flags: AdvertisingFlags::unpack(&data[1..5])?,
+            max_advertising_data_length: data[5],
+            max_scan_response_length: data[6],
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pack_add() {
+        let mut command = AddAdvertising::new(
+            1,
+            AdvertisingFlags::CONNECTABLE | AdvertisingFlags::LOCAL_NAME,
+            vec![0x02, 0x01, 0x06],
+            vec![0x03, 0x19, 0x40, 0x00],
+        );
+        command.duration = Duration::from_millis(2_400);
+        command.timeout = Duration::from_secs(300);
+        let mut data = [0u8; 18];
+        assert_eq!(command.pack(&mut data).unwrap(), 18);
+        assert_eq!(
+            data,
+            [
+                0x01, 0x41, 0x00, 0x00, 0x00, 0x03, 0x00, 0x2c, 0x01, 0x03, 0x04, 0x02, 0x01, 0x06,
+                0x03, 0x19, 0x40, 0x00
+            ]
+        );
+        assert!(command.pack(&mut data[..17]).is_err());
+
+        command.duration = Duration::from_secs(0);
+        command.timeout = Duration::from_millis(400);
+        command.pack(&mut data).unwrap();
+        assert_eq!(&data[5..9], &[0x00, 0x00, 0x01, 0x00]);
+
+        command.timeout = Duration::from_secs(70_000);
+        assert!(command.pack(&mut data).is_err());
+        command.timeout = Duration::from_secs(0);
+        command.instance = 0;
+        assert!(command.pack(&mut data).is_err());
+    }
+
+    #[test]
+    fn pack_remove() {
+        let mut data = [0xffu8; 1];
+        assert_eq!(RemoveAdvertising::all().pack(&mut data).unwrap(), 1);
+        assert_eq!(data, [0x00]);
+        assert_eq!(RemoveAdvertising::unpack(&[0x03]).unwrap(), 3);
+    }
+
+    #[test]
+    fn unpack_features() {
+        let data = [0xff, 0x03, 0x00, 0x00, 0x1f, 0x1f, 0x05, 0x02, 0x01, 0x03];
+        let features = ReadAdvancedFeatures::unpack(&data).unwrap();
+        assert_eq!(features, AdvertisingFeatures::unpack(&data).unwrap());
+        assert!(features
+            .supported_flags
+            .contains(AdvertisingFlags::SECONDARY_CODED));
+        assert_eq!(features.max_advertising_data_length, 31);
+        assert_eq!(features.max_scan_response_length, 31);
+        assert_eq!(features.max_instances, 5);
+        assert_eq!(features.instances, vec![1, 3]);
+        assert!(AdvertisingFeatures::unpack(&[
+            0xff, 0x03, 0x00, 0x00, 0x1f, 0x1f, 0x05, 0x02, 0x01
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn size_information() {
+        let command = GetAdvertisingSizeInformation::new(2, AdvertisingFlags::TX_POWER);
+        let mut data = [0u8; 5];
+        assert_eq!(command.pack(&mut data).unwrap(), 5);
+        assert_eq!(data, [0x02, 0x10, 0x00, 0x00, 0x00]);
+
+        let data = [0x02, 0x10, 0x00, 0x00, 0x00, 0x1c, 0x1f];
+        let information = GetAdvertisingSizeInformation::unpack(&data).unwrap();
+        assert_eq!(
+            information,
+            AdvertisingSizeInformation::unpack(&data).unwrap()
+        );
+        assert_eq!(information.instance, 2);
+        assert_eq!(information.flags, AdvertisingFlags::TX_POWER);
+        assert_eq!(information.max_advertising_data_length, 28);
+        assert_eq!(information.max_scan_response_length, 31);
+    }
+}
@@ -0,0 +1,35 @@
+use std::convert::TryFrom;
+
+use byteorder::{ByteOrder, LittleEndian};
+
+use crate::{error::HciError, error::HciErrorKind, pack::Pack, Appearance, Error, OperationId};
+
+use super::Command;
+
+/// Set the appearance of a controller, used in LE advertising
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SetAppearance {
+    appearance: u16,
+}
+
+impl SetAppearance {
+    /// Create a new command, fails for reserved appearances
+    pub fn new(appearance: Appearance) -> Result<SetAppearance, Error> {
+        let appearance = u16::try_from(appearance)?;
+        Ok(SetAppearance { appearance })
+    }
+}
+
+impl Pack<SetAppearance, Error> for SetAppearance {
+    fn pack(&self, data: &mut [u8]) -> Result<usize, Error> {
+        if data.len() < 2 {
+            return Err(Error::from(HciError::new(HciErrorKind::InvalidLength)));
+        }
+        LittleEndian::write_u16(&mut data[0..2], self.appearance);
+        Ok(2)
+    }
+}
+
+impl Command for SetAppearance {
+    const OPERATION: OperationId = OperationId::SetAppearance;
+}
@@ -0,0 +1,13 @@
+address_command!(
+    /// Block a device, rejecting any connections from it
+    ///
+    /// The reply holds the address of the device.
+    BlockDevice
+);
+
+address_command!(
+    /// Unblock a previously blocked device
+    ///
+    /// The reply holds the address of the device.
+    UnblockDevice
+);
@@ -0,0 +1,81 @@
+use crate::{
+    error::HciError,
+    error::HciErrorKind,
+    pack::{Pack, PackFixed, UnpackFixed},
+    ClassOfDevice, Error, OperationId,
+};
+
+use super::Command;
+
+/// Set the major and minor device class of a controller
+///
+/// The major service classes are not set by this command, those are derived
+/// from the service hints given when adding UUIDs. The reply holds the
+/// resulting class of device.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SetDeviceClass {
+    class_of_device: ClassOfDevice,
+}
+
+impl SetDeviceClass {
+    /// Create a command setting the device class of the given class of device
+    pub fn new(class_of_device: ClassOfDevice) -> SetDeviceClass {
+        SetDeviceClass { class_of_device }
+    }
+}
+
+impl Pack<SetDeviceClass, Error> for SetDeviceClass {
+    fn pack(&self, data: &mut [u8]) -> Result<usize, Error> {
+        if data.len() < 2 {
+            return Err(Error::from(HciError::new(HciErrorKind::InvalidLength)));
+        }
+        let mut class_of_device = [0u8; 3];
+        self.class_of_device.pack(&mut class_of_device)?;
+        data[0] = class_of_device[1] & 0b0001_1111;
+        data[1] = class_of_device[0] & 0b1111_1100;
+        Ok(2)
+    }
+}
+
+impl<'a> UnpackFixed<'a, ClassOfDevice, Error> for SetDeviceClass {
+    fn unpack(data: &'a [u8]) -> Result<ClassOfDevice, Error> {
+        ClassOfDevice::unpack(data)
+    }
+}
+
+impl Command for SetDeviceClass {
+    const OPERATION: OperationId = OperationId::SetDeviceClass;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::class_of_device::{DeviceClass, DeviceClassPhone, MajorDeviceClass};
+
+    #[test]
+    fn pack() {
+        let class_of_device = ClassOfDevice::builder(MajorDeviceClass::Phone)
+            .device_class(DeviceClass::Phone(DeviceClassPhone::Smartphone))
+            .build()
+            .unwrap();
+        let command = SetDeviceClass::new(class_of_device);
+        let mut data = [0u8; 2];
+        assert_eq!(command.pack(&mut data).unwrap(), 2);
+        assert_eq!(data, [0x02, 0x0c]);
+
+        let class_of_device = ClassOfDevice::unpack(&[0x0f, 0x02, 0x40]).unwrap();
+        let command = SetDeviceClass::new(class_of_device);
+        command.pack(&mut data).unwrap();
+        assert_eq!(data, [0x02, 0x0c]);
+    }
+
+    #[test]
+    fn unpack_reply() {
+        let class_of_device = SetDeviceClass::unpack(&[0x0c, 0x02, 0x40]).unwrap();
+        assert_eq!(
+            class_of_device.device_class(),
+            DeviceClass::Phone(DeviceClassPhone::Smartphone)
+        );
+        assert!(SetDeviceClass::unpack(&[0x0c, 0x02]).is_err());
+    }
+}
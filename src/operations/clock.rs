@@ -0,0 +1,131 @@
+use std::time::Duration;
+
+use byteorder::{ByteOrder, LittleEndian};
+
+use crate::{
+    address_info::AddressType,
+    error::HciError,
+    error::HciErrorKind,
+    pack::{Pack, PackFixed, UnpackFixed},
+    AddressInfo, Error, HardwareAddress, OperationId,
+};
+
+use super::Command;
+
+/// Bluetooth clock value, counted in ticks of 312.5 µs
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Clock(pub u32);
+
+impl Clock {
+    /// Duration of one clock tick in nanoseconds
+    pub const TICK_NANOS: u64 = 312_500;
+
+    /// Number of clock ticks
+    pub fn ticks(self) -> u32 {
+        self.0
+    }
+
+    /// Clock value as duration
+    pub fn duration(self) -> Duration {
+        Duration::from_nanos(u64::from(self.0) * Clock::TICK_NANOS)
+    }
+}
+
+/// Get the local clock and the piconet clock of a connection
+///
+/// The reply is a `ClockInformation`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct GetClockInformation {
+    pub address_info: AddressInfo,
+}
+
+impl GetClockInformation {
+    /// Create a command reading the local clock and the piconet clock of the
+    /// connection with the given BR/EDR device
+    pub fn new(address_info: AddressInfo) -> GetClockInformation {
+        GetClockInformation { address_info }
+    }
+
+    /// Create a command reading the local clock only
+    pub fn local() -> GetClockInformation {
+        GetClockInformation {
+            address_info: AddressInfo {
+                address: HardwareAddress::from([0u8; 6]),
+                address_type: AddressType::BrEdr,
+            },
+        }
+    }
+}
+
+impl Pack<GetClockInformation, Error> for GetClockInformation {
+    fn pack(&self, data: &mut [u8]) -> Result<usize, Error> {
+        if data.len() < AddressInfo::SIZE {
+            return Err(Error::from(HciError::new(HciErrorKind::InvalidLength)));
+        }
+        self.address_info.pack(&mut data[..AddressInfo::SIZE])?;
+        Ok(AddressInfo::SIZE)
+    }
+}
+
+impl<'a> UnpackFixed<'a, ClockInformation, Error> for GetClockInformation {
+    fn unpack(data: &'a [u8]) -> Result<ClockInformation, Error> {
+        ClockInformation::unpack(data)
+    }
+}
+
+impl Command for GetClockInformation {
+    const OPERATION: OperationId = OperationId::GetClockInformation;
+}
+
+/// Local and piconet clock information
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ClockInformation {
+    pub address_info: AddressInfo,
+    pub local_clock: Clock,
+    pub piconet_clock: Clock,
+    /// Accuracy of the piconet clock, plus or minus
+    pub accuracy: Clock,
+}
+
+impl<'a> UnpackFixed<'a, ClockInformation, Error> for ClockInformation {
+    fn unpack(data: &'a [u8]) -> Result<ClockInformation, Error> {
+        if data.len() != AddressInfo::SIZE + 10 {
+            return Err(Error::from(HciError::new(HciErrorKind::NotEnoughData)));
+        }
+        let address_info = AddressInfo::unpack(&data[..AddressInfo::SIZE])?;
+        let mut offset = AddressInfo::SIZE;
+        let local_clock = Clock(LittleEndian::read_u32(&data[offset..offset + 4]));
+        offset += 4;
+        let piconet_clock = Clock(LittleEndian::read_u32(&data[offset..offset + 4]));
+        offset += 4;
+        let accuracy = Clock(u32::from(LittleEndian::read_u16(&data[offset..offset + 2])));
+        Ok(ClockInformation {
+            address_info,
+            local_clock,
+            piconet_clock,
+            accuracy,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clock_information() {
+        let data = [
+            0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x00, 0x20, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00,
+            0x00, 0x02, 0x00,
+        ];
+        let information = GetClockInformation::unpack(&data).unwrap();
+        assert_eq!(information, ClockInformation::unpack(&data).unwrap());
+        assert_eq!(information.local_clock, Clock(32));
+        assert_eq!(
+            information.local_clock.duration(),
+            Duration::from_millis(10)
+        );
+        assert_eq!(information.piconet_clock.ticks(), 256);
+        assert_eq!(information.accuracy.duration(), Duration::from_micros(625));
+    }
+}
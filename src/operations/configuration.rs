@@ -0,0 +1,181 @@
+use byteorder::{ByteOrder, LittleEndian};
+
+use crate::{
+    error::HciError,
+    error::HciErrorKind,
+    pack::{Pack, UnpackFixed},
+    Error, HardwareAddress, OperationId,
+};
+
+use super::Command;
+
+bitflags!(
+    pub struct ConfigurationOptions: u32 {
+        const EXTERNAL_CONFIGURATION = 0x0000_0001;
+        const PUBLIC_ADDRESS = 0x0000_0002;
+    }
+);
+
+impl<'a> UnpackFixed<'a, ConfigurationOptions, Error> for ConfigurationOptions {
+    /// Unpack configuration options, ignoring options unknown to this crate
+    fn unpack(data: &'a [u8]) -> Result<ConfigurationOptions, Error> {
+        if data.len() != 4 {
+            return Err(Error::from(HciError::new(HciErrorKind::NotEnoughData)));
+        }
+        Ok(ConfigurationOptions::from_bits_truncate(
+            LittleEndian::read_u32(data),
+        ))
+    }
+}
+
+/// Read the configuration options of an unconfigured controller
+///
+/// The reply is a `ConfigurationInformation`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ReadConfigurationInformation;
+
+impl Pack<ReadConfigurationInformation, Error> for ReadConfigurationInformation {
+    fn pack(&self, _data: &mut [u8]) -> Result<usize, Error> {
+        Ok(0)
+    }
+}
+
+impl<'a> UnpackFixed<'a, ConfigurationInformation, Error> for ReadConfigurationInformation {
+    fn unpack(data: &'a [u8]) -> Result<ConfigurationInformation, Error> {
+        ConfigurationInformation::unpack(data)
+    }
+}
+
+impl Command for ReadConfigurationInformation {
+    const OPERATION: OperationId = OperationId::ReadConfigurationInformation;
+}
+
+/// Configuration information of a controller
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ConfigurationInformation {
+    pub manufacturer: u16,
+    pub supported_options: ConfigurationOptions,
+    /// Options that must be configured before the controller can be used
+    pub missing_options: ConfigurationOptions,
+}
+
+impl<'a> UnpackFixed<'a, ConfigurationInformation, Error> for ConfigurationInformation {
+    fn unpack(data: &'a [u8]) -> Result<ConfigurationInformation, Error> {
+        if data.len() != 10 {
+            return Err(Error::from(HciError::new(HciErrorKind::NotEnoughData)));
+        }
+        Ok(ConfigurationInformation {
+            manufacturer: LittleEndian::read_u16(&data[0..2]),
+            supported_options: ConfigurationOptions::unpack(&data[2..6])?,
+            missing_options: ConfigurationOptions::unpack(&data[6..10])?,
+        })
+    }
+}
+
+/// Enable or disable external configuration of a controller
+///
+/// The reply holds the missing configuration options.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SetExternalConfiguration {
+    pub configured: bool,
+}
+
+impl SetExternalConfiguration {
+    /// Create a new command
+    pub fn new(configured: bool) -> SetExternalConfiguration {
+        SetExternalConfiguration { configured }
+    }
+}
+
+impl Pack<SetExternalConfiguration, Error> for SetExternalConfiguration {
+    fn pack(&self, data: &mut [u8]) -> Result<usize, Error> {
+        if data.is_empty() {
+            return Err(Error::from(HciError::new(HciErrorKind::InvalidLength)));
+        }
+        data[0] = u8::from(self.configured);
+        Ok(1)
+    }
+}
+
+impl<'a> UnpackFixed<'a, ConfigurationOptions, Error> for SetExternalConfiguration {
+    fn unpack(data: &'a [u8]) -> Result<ConfigurationOptions, Error> {
+        ConfigurationOptions::unpack(data)
+    }
+}
+
+impl Command for SetExternalConfiguration {
+    const OPERATION: OperationId = OperationId::SetExternalConfiguration;
+}
+
+/// Set the public address of a controller lacking one
+///
+/// The reply holds the missing configuration options.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SetPublicAddress {
+    pub address: HardwareAddress,
+}
+
+impl SetPublicAddress {
+    /// Create a new command
+    pub fn new(address: HardwareAddress) -> SetPublicAddress {
+        SetPublicAddress { address }
+    }
+}
+
+impl Pack<SetPublicAddress, Error> for SetPublicAddress {
+    fn pack(&self, data: &mut [u8]) -> Result<usize, Error> {
+        if data.len() < 6 {
+            return Err(Error::from(HciError::new(HciErrorKind::InvalidLength)));
+        }
+        data[..6].copy_from_slice(&self.address.bytes());
+        Ok(6)
+    }
+}
+
+impl<'a> UnpackFixed<'a, ConfigurationOptions, Error> for SetPublicAddress {
+    fn unpack(data: &'a [u8]) -> Result<ConfigurationOptions, Error> {
+        ConfigurationOptions::unpack(data)
+    }
+}
+
+impl Command for SetPublicAddress {
+    const OPERATION: OperationId = OperationId::SetPublicAddress;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn configuration_information() {
+        let data = [0x0f, 0x00, 0x03, 0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x00];
+        let information = ReadConfigurationInformation::unpack(&data).unwrap();
+        assert_eq!(
+            information,
+            ConfigurationInformation::unpack(&data).unwrap()
+        );
+        assert_eq!(information.manufacturer, 0x000f);
+        assert_eq!(information.supported_options, ConfigurationOptions::all());
+        assert_eq!(
+            information.missing_options,
+            ConfigurationOptions::PUBLIC_ADDRESS
+        );
+        assert_eq!(
+            ConfigurationOptions::unpack(&[0x06, 0x00, 0x00, 0x00]).unwrap(),
+            ConfigurationOptions::PUBLIC_ADDRESS
+        );
+        assert!(ConfigurationOptions::unpack(&[0x06, 0x00, 0x00]).is_err());
+    }
+
+    #[test]
+    fn set_public_address() {
+        let address = HardwareAddress::from([0x01, 0x02, 0x03, 0x04, 0x05, 0x06]);
+        let mut data = [0u8; 6];
+        assert_eq!(SetPublicAddress::new(address).pack(&mut data).unwrap(), 6);
+        assert_eq!(data, address.bytes());
+        assert_eq!(
+            SetPublicAddress::unpack(&[0x00, 0x00, 0x00, 0x00]).unwrap(),
+            ConfigurationOptions::empty()
+        );
+    }
+}
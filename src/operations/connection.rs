@@ -0,0 +1,172 @@
+use byteorder::{ByteOrder, LittleEndian};
+
+use crate::{
+    error::HciError,
+    error::HciErrorKind,
+    pack::{Pack, PackFixed, UnpackFixed},
+    AddressInfo, Error, OperationId,
+};
+
+use super::Command;
+
+address_command!(
+    /// Disconnect a connected device
+    ///
+    /// The reply holds the address of the disconnected device.
+    Disconnect
+);
+
+/// List the connected devices
+///
+/// The reply holds the addresses of the connected devices.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct GetConnections;
+
+impl Pack<GetConnections, Error> for GetConnections {
+    fn pack(&self, _data: &mut [u8]) -> Result<usize, Error> {
+        Ok(0)
+    }
+}
+
+impl<'a> UnpackFixed<'a, Vec<AddressInfo>, Error> for GetConnections {
+    fn unpack(data: &'a [u8]) -> Result<Vec<AddressInfo>, Error> {
+        if data.len() < 2 {
+            return Err(Error::from(HciError::new(HciErrorKind::NotEnoughData)));
+        }
+        let count = LittleEndian::read_u16(&data[0..2]) as usize;
+        let end = 2 + count * AddressInfo::SIZE;
+        if data.len() != end {
+            return Err(Error::from(HciError::new(HciErrorKind::InvalidLength)));
+        }
+        data[2..end]
+            .chunks_exact(AddressInfo::SIZE)
+            .map(AddressInfo::unpack)
+            .collect()
+    }
+}
+
+impl Command for GetConnections {
+    const OPERATION: OperationId = OperationId::GetConnections;
+}
+
+/// Value used for unknown RSSI and transmit power levels
+const INVALID_POWER: i8 = 127;
+
+fn unpack_power(value: u8) -> Option<i8> {
+    match value as i8 {
+        INVALID_POWER => None,
+        value => Some(value),
+    }
+}
+
+/// Get link quality information of a connected device
+///
+/// The reply is a `ConnectionInformation`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct GetConnectionInformation {
+    pub address_info: AddressInfo,
+}
+
+impl GetConnectionInformation {
+    /// Create a new command
+    pub fn new(address_info: AddressInfo) -> GetConnectionInformation {
+        GetConnectionInformation { address_info }
+    }
+}
+
+impl Pack<GetConnectionInformation, Error> for GetConnectionInformation {
+    fn pack(&self, data: &mut [u8]) -> Result<usize, Error> {
+        if data.len() < AddressInfo::SIZE {
+            return Err(Error::from(HciError::new(HciErrorKind::InvalidLength)));
+        }
+        self.address_info.pack(&mut data[..AddressInfo::SIZE])?;
+        Ok(AddressInfo::SIZE)
+    }
+}
+
+impl<'a> UnpackFixed<'a, ConnectionInformation, Error> for GetConnectionInformation {
+    fn unpack(data: &'a [u8]) -> Result<ConnectionInformation, Error> {
+        ConnectionInformation::unpack(data)
+    }
+}
+
+impl Command for GetConnectionInformation {
+    const OPERATION: OperationId = OperationId::GetConnectionInformation;
+}
+
+/// Link quality information of a connection
+///
+/// Values not known by the controller are `None`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ConnectionInformation {
+    pub address_info: AddressInfo,
+    /// Received signal strength in dBm
+    pub rssi: Option<i8>,
+    /// Transmit power level in dBm
+    pub tx_power: Option<i8>,
+    /// Maximum transmit power level in dBm
+    pub max_tx_power: Option<i8>,
+}
+
+impl<'a> UnpackFixed<'a, ConnectionInformation, Error> for ConnectionInformation {
+    fn unpack(data: &'a [u8]) -> Result<ConnectionInformation, Error> {
+        if data.len() != AddressInfo::SIZE + 3 {
+            return Err(Error::from(HciError::new(HciErrorKind::NotEnoughData)));
+        }
+        let address_info = AddressInfo::unpack(&data[..AddressInfo::SIZE])?;
+        Ok(ConnectionInformation {
+            address_info,
+            rssi: unpack_power(data[7]),
+            tx_power: unpack_power(data[8]),
+            max_tx_power: unpack_power(data[9]),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AddressType, HardwareAddress};
+
+    #[test]
+    fn disconnect() {
+        let address_info = AddressInfo {
+            address: HardwareAddress::from([0x01, 0x02, 0x03, 0x04, 0x05, 0x06]),
+            address_type: AddressType::BrEdr,
+        };
+        let mut data = [0u8; 7];
+        assert_eq!(Disconnect::new(address_info).pack(&mut data).unwrap(), 7);
+        assert_eq!(data, [0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x00]);
+        assert_eq!(Disconnect::unpack(&data).unwrap(), address_info);
+    }
+
+    #[test]
+    fn get_connections() {
+        let connections = GetConnections::unpack(&[
+            0x02, 0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x00, 0x11, 0x12, 0x13, 0x14, 0x15,
+            0xd6, 0x02,
+        ])
+        .unwrap();
+        assert_eq!(connections.len(), 2);
+        assert_eq!(connections[0].address_type, AddressType::BrEdr);
+        assert_eq!(
+            connections[1].address,
+            HardwareAddress::from([0x11, 0x12, 0x13, 0x14, 0x15, 0xd6])
+        );
+        assert_eq!(connections[1].address_type, AddressType::LeRandom);
+
+        assert!(GetConnections::unpack(&[0x00, 0x00]).unwrap().is_empty());
+        assert!(GetConnections::unpack(&[0x01, 0x00]).is_err());
+    }
+
+    #[test]
+    fn connection_information() {
+        let data = [0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x01, 0xc4, 0x7f, 0x04];
+        let information = GetConnectionInformation::unpack(&data).unwrap();
+        assert_eq!(information, ConnectionInformation::unpack(&data).unwrap());
+        assert_eq!(information.address_info.address_type, AddressType::LePublic);
+        assert_eq!(information.rssi, Some(-60));
+        assert_eq!(information.tx_power, None);
+        assert_eq!(information.max_tx_power, Some(4));
+    }
+}
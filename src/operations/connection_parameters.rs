@@ -0,0 +1,276 @@
+use std::time::Duration;
+
+use byteorder::{ByteOrder, LittleEndian};
+
+use crate::{
+    error::HciError,
+    error::HciErrorKind,
+    pack::{Pack, PackFixed, UnpackFixed},
+    socket::MGMT_MAX_PARAMETERS_SIZE,
+    AddressInfo, Error, OperationId,
+};
+
+use super::{duration_to_units, units_to_duration, Command};
+
+/// Length of a connection interval unit, 1.25 ms
+const INTERVAL_UNIT_NANOS: u64 = 1_250_000;
+/// Length of a supervision timeout unit, 10 ms
+const TIMEOUT_UNIT_NANOS: u64 = 10_000_000;
+const MIN_INTERVAL_UNITS: u16 = 0x0006;
+const MAX_INTERVAL_UNITS: u16 = 0x0c80;
+const MAX_LATENCY: u16 = 0x01f3;
+const MIN_TIMEOUT_UNITS: u16 = 0x000a;
+const MAX_TIMEOUT_UNITS: u16 = 0x0c80;
+
+/// Convert a duration to a number of units, fails if it does not fit
+fn to_units(duration: Duration, unit_nanos: u64) -> Result<u16, Error> {
+    let units = duration_to_units(duration, unit_nanos);
+    if units > u128::from(u16::MAX) {
+        return Err(Error::from(HciError::new(HciErrorKind::InvalidValue)));
+    }
+    Ok(units as u16)
+}
+
+/// Preferred LE connection parameters of a device
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ConnectionParameters {
+    address_info: AddressInfo,
+    min_interval: u16,
+    max_interval: u16,
+    latency: u16,
+    timeout: u16,
+}
+
+impl ConnectionParameters {
+    /// Size of packed connection parameters in octets
+    pub const SIZE: usize = AddressInfo::SIZE + 8;
+
+    /// Create new connection parameters
+    ///
+    /// The intervals are rounded to the nearest 1.25 ms and the supervision
+    /// timeout to the nearest 10 ms before validation.
+    pub fn new(
+        address_info: AddressInfo,
+        min_interval: Duration,
+        max_interval: Duration,
+        latency: u16,
+        timeout: Duration,
+    ) -> Result<ConnectionParameters, Error> {
+        ConnectionParameters::from_units(
+            address_info,
+            to_units(min_interval, INTERVAL_UNIT_NANOS)?,
+            to_units(max_interval, INTERVAL_UNIT_NANOS)?,
+            latency,
+            to_units(timeout, TIMEOUT_UNIT_NANOS)?,
+        )
+    }
+
+    /// Create new connection parameters from intervals in units of 1.25 ms
+    /// and a supervision timeout in units of 10 ms
+    ///
+    /// Fails if the intervals are outside 7.5 ms to 4 s or the minimum is
+    /// larger than the maximum, if the latency is larger than 499, if the
+    /// timeout is outside 100 ms to 32 s, or if the timeout is not larger
+    /// than (1 + latency) * max interval * 2.
+    pub fn from_units(
+        address_info: AddressInfo,
+        min_interval: u16,
+        max_interval: u16,
+        latency: u16,
+        timeout: u16,
+    ) -> Result<ConnectionParameters, Error> {
+        let intervals = MIN_INTERVAL_UNITS..=MAX_INTERVAL_UNITS;
+        let timeouts = MIN_TIMEOUT_UNITS..=MAX_TIMEOUT_UNITS;
+        if !intervals.contains(&min_interval)
+            || !intervals.contains(&max_interval)
+            || min_interval > max_interval
+            || latency > MAX_LATENCY
+            || !timeouts.contains(&timeout)
+            // timeout * 10 ms > (1 + latency) * max_interval * 1.25 ms * 2
+            || u32::from(timeout) * 4 <= (1 + u32::from(latency)) * u32::from(max_interval)
+        {
+            return Err(Error::from(HciError::new(HciErrorKind::InvalidValue)));
+        }
+        Ok(ConnectionParameters {
+            address_info,
+            min_interval,
+            max_interval,
+            latency,
+            timeout,
+        })
+    }
+
+    /// Address of the device
+    pub fn address_info(&self) -> AddressInfo {
+        self.address_info
+    }
+
+    /// Minimum connection interval
+    pub fn min_interval(&self) -> Duration {
+        units_to_duration(u64::from(self.min_interval), INTERVAL_UNIT_NANOS)
+    }
+
+    /// Maximum connection interval
+    pub fn max_interval(&self) -> Duration {
+        units_to_duration(u64::from(self.max_interval), INTERVAL_UNIT_NANOS)
+    }
+
+    /// Peripheral latency in number of connection events
+    pub fn latency(&self) -> u16 {
+        self.latency
+    }
+
+    /// Supervision timeout
+    pub fn timeout(&self) -> Duration {
+        units_to_duration(u64::from(self.timeout), TIMEOUT_UNIT_NANOS)
+    }
+}
+
+impl PackFixed<ConnectionParameters, Error> for ConnectionParameters {
+    fn pack(&self, data: &mut [u8]) -> Result<(), Error> {
+        if data.len() != ConnectionParameters::SIZE {
+            return Err(Error::from(HciError::new(HciErrorKind::InvalidLength)));
+        }
+        self.address_info.pack(&mut data[..AddressInfo::SIZE])?;
+        let data = &mut data[AddressInfo::SIZE..];
+        LittleEndian::write_u16(&mut data[0..2], self.min_interval);
+        LittleEndian::write_u16(&mut data[2..4], self.max_interval);
+        LittleEndian::write_u16(&mut data[4..6], self.latency);
+        LittleEndian::write_u16(&mut data[6..8], self.timeout);
+        Ok(())
+    }
+}
+
+impl<'a> UnpackFixed<'a, ConnectionParameters, Error> for ConnectionParameters {
+    /// Unpack connection parameters reported by the kernel
+    ///
+    /// The values are not validated, the kernel accepts parameters that
+    /// `from_units` rejects, for example a supervision timeout equal to
+    /// (1 + latency) * max interval * 2.
+    fn unpack(data: &'a [u8]) -> Result<ConnectionParameters, Error> {
+        if data.len() != ConnectionParameters::SIZE {
+            return Err(Error::from(HciError::new(HciErrorKind::NotEnoughData)));
+        }
+        let address_info = AddressInfo::unpack(&data[..AddressInfo::SIZE])?;
+        let data = &data[AddressInfo::SIZE..];
+        Ok(ConnectionParameters {
+            address_info,
+            min_interval: LittleEndian::read_u16(&data[0..2]),
+            max_interval: LittleEndian::read_u16(&data[2..4]),
+            latency: LittleEndian::read_u16(&data[4..6]),
+            timeout: LittleEndian::read_u16(&data[6..8]),
+        })
+    }
+}
+
+/// Load the preferred LE connection parameters of devices
+///
+/// Parameters of devices not in the list are kept by the kernel.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LoadConnectionParameters {
+    pub parameters: Vec<ConnectionParameters>,
+}
+
+impl LoadConnectionParameters {
+    /// Maximum number of records fitting in a command
+    pub const MAX_PARAMETERS: usize = (MGMT_MAX_PARAMETERS_SIZE - 2) / ConnectionParameters::SIZE;
+
+    /// Create a new command
+    pub fn new(parameters: Vec<ConnectionParameters>) -> LoadConnectionParameters {
+        LoadConnectionParameters { parameters }
+    }
+}
+
+impl Pack<LoadConnectionParameters, Error> for LoadConnectionParameters {
+    fn pack(&self, data: &mut [u8]) -> Result<usize, Error> {
+        if self.parameters.len() > LoadConnectionParameters::MAX_PARAMETERS {
+            return Err(Error::from(HciError::new(HciErrorKind::InvalidLength)));
+        }
+        let size = 2 + self.parameters.len() * ConnectionParameters::SIZE;
+        if data.len() < size {
+            return Err(Error::from(HciError::new(HciErrorKind::InvalidLength)));
+        }
+        LittleEndian::write_u16(&mut data[0..2], self.parameters.len() as u16);
+        for (parameters, chunk) in self
+            .parameters
+            .iter()
+            .zip(data[2..size].chunks_exact_mut(ConnectionParameters::SIZE))
+        {
+            parameters.pack(chunk)?;
+        }
+        Ok(size)
+    }
+}
+
+impl Command for LoadConnectionParameters {
+    const OPERATION: OperationId = OperationId::LoadConnectionParameters;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AddressType, HardwareAddress};
+
+    #[test]
+    fn new() {
+        let address_info = AddressInfo {
+            address: HardwareAddress::from([0x01, 0x02, 0x03, 0x04, 0x05, 0x06]),
+            address_type: AddressType::LePublic,
+        };
+        let ms = Duration::from_millis;
+        let parameters =
+            ConnectionParameters::new(address_info, ms(30), ms(50), 4, ms(4_000)).unwrap();
+        assert_eq!(parameters.min_interval(), ms(30));
+        assert_eq!(parameters.max_interval(), ms(50));
+        assert_eq!(parameters.latency(), 4);
+        assert_eq!(parameters.timeout(), ms(4_000));
+    }
+
+    #[test]
+    fn validate() {
+        let address_info = AddressInfo {
+            address: HardwareAddress::from([0x01, 0x02, 0x03, 0x04, 0x05, 0x06]),
+            address_type: AddressType::LePublic,
+        };
+        let ms = Duration::from_millis;
+        let new = |min, max, latency, timeout| {
+            ConnectionParameters::new(address_info, ms(min), ms(max), latency, ms(timeout))
+        };
+        assert!(new(5, 50, 0, 1_000).is_err());
+        assert!(new(50, 4_010, 0, 10_000).is_err());
+        assert!(new(60, 50, 0, 1_000).is_err());
+        assert!(new(30, 50, 500, 32_000).is_err());
+        assert!(new(30, 50, 0, 90).is_err());
+        assert!(new(30, 50, 0, 32_010).is_err());
+        assert!(new(30, 50, 0, 70_000_000).is_err());
+        // (1 + 4) * 50 ms * 2 = 500 ms
+        assert!(new(30, 50, 4, 500).is_err());
+        assert!(new(30, 50, 4, 510).is_ok());
+    }
+
+    #[test]
+    fn pack_unpack() {
+        let address_info = AddressInfo {
+            address: HardwareAddress::from([0x01, 0x02, 0x03, 0x04, 0x05, 0x06]),
+            address_type: AddressType::LePublic,
+        };
+        let parameters = ConnectionParameters::from_units(address_info, 24, 40, 0, 42).unwrap();
+        let command = LoadConnectionParameters::new(vec![parameters; 2]);
+        let mut data = [0u8; 32];
+        assert_eq!(command.pack(&mut data).unwrap(), 32);
+        assert_eq!(&data[..2], &[0x02, 0x00]);
+        assert_eq!(
+            &data[2..17],
+            &[
+                0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x01, 0x18, 0x00, 0x28, 0x00, 0x00, 0x00, 0x2a,
+                0x00
+            ]
+        );
+        assert_eq!(&data[17..], &data[2..17]);
+        assert_eq!(
+            ConnectionParameters::unpack(&data[2..17]).unwrap(),
+            parameters
+        );
+        assert!(command.pack(&mut data[..31]).is_err());
+    }
+}
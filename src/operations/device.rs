@@ -0,0 +1,98 @@
+use crate::{
+    error::HciError,
+    error::HciErrorKind,
+    pack::{Pack, PackFixed, UnpackFixed},
+    AddressInfo, AddressType, Error, HardwareAddress, OperationId,
+};
+
+use super::Command;
+
+extended_enum_other!(
+    DeviceAction, u8,
+    BackgroundScan => 0x00,
+    AllowIncoming => 0x01,
+    AutoConnect => 0x02,
+);
+
+/// Add a device to the list of devices the kernel connects to or accepts
+/// connections from
+///
+/// With `BackgroundScan` the device is reported through DeviceFound events,
+/// `AllowIncoming` accepts incoming BR/EDR connections and `AutoConnect`
+/// connects to an LE device whenever it is advertising. The reply holds the
+/// address of the device.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct AddDevice {
+    pub address_info: AddressInfo,
+    pub action: DeviceAction,
+}
+
+impl AddDevice {
+    /// Create a new command
+    pub fn new(address_info: AddressInfo, action: DeviceAction) -> AddDevice {
+        AddDevice {
+            address_info,
+            action,
+        }
+    }
+}
+
+impl Pack<AddDevice, Error> for AddDevice {
+    fn pack(&self, data: &mut [u8]) -> Result<usize, Error> {
+        if data.len() < AddressInfo::SIZE + 1 {
+            return Err(Error::from(HciError::new(HciErrorKind::InvalidLength)));
+        }
+        self.address_info.pack(&mut data[..AddressInfo::SIZE])?;
+        data[AddressInfo::SIZE] = u8::from(self.action);
+        Ok(AddressInfo::SIZE + 1)
+    }
+}
+
+impl<'a> UnpackFixed<'a, AddressInfo, Error> for AddDevice {
+    fn unpack(data: &'a [u8]) -> Result<AddressInfo, Error> {
+        AddressInfo::unpack(data)
+    }
+}
+
+impl Command for AddDevice {
+    const OPERATION: OperationId = OperationId::AddDevice;
+}
+
+address_command!(
+    /// Remove a device added with AddDevice
+    ///
+    /// The reply holds the address of the device.
+    RemoveDevice
+);
+
+impl RemoveDevice {
+    /// Create a command removing all devices
+    pub fn all() -> RemoveDevice {
+        RemoveDevice::new(AddressInfo {
+            address: HardwareAddress::from([0u8; 6]),
+            address_type: AddressType::BrEdr,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pack_unpack() {
+        let address_info = AddressInfo {
+            address: HardwareAddress::from([0x01, 0x02, 0x03, 0x04, 0x05, 0xc6]),
+            address_type: AddressType::LeRandom,
+        };
+        let mut data = [0xffu8; 8];
+        let command = AddDevice::new(address_info, DeviceAction::AutoConnect);
+        assert_eq!(command.pack(&mut data).unwrap(), 8);
+        assert_eq!(data, [0x01, 0x02, 0x03, 0x04, 0x05, 0xc6, 0x02, 0x02]);
+        assert_eq!(AddDevice::unpack(&data[..7]).unwrap(), address_info);
+        assert!(command.pack(&mut data[..7]).is_err());
+
+        assert_eq!(RemoveDevice::all().pack(&mut data).unwrap(), 7);
+        assert_eq!(&data[..7], &[0u8; 7]);
+    }
+}
@@ -0,0 +1,62 @@
+use crate::{
+    common::device_id::DeviceId,
+    error::HciError,
+    error::HciErrorKind,
+    pack::{Pack, PackFixed},
+    Error, OperationId,
+};
+
+use super::Command;
+
+/// Set the Device ID record published in the extended inquiry response
+///
+/// A record with source `Disabled` removes the record.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SetDeviceIdentifier {
+    pub device_id: DeviceId,
+}
+
+impl SetDeviceIdentifier {
+    /// Create a new command
+    pub fn new(device_id: DeviceId) -> SetDeviceIdentifier {
+        SetDeviceIdentifier { device_id }
+    }
+}
+
+impl Pack<SetDeviceIdentifier, Error> for SetDeviceIdentifier {
+    fn pack(&self, data: &mut [u8]) -> Result<usize, Error> {
+        if data.len() < DeviceId::SIZE {
+            return Err(Error::from(HciError::new(HciErrorKind::InvalidLength)));
+        }
+        self.device_id.pack(&mut data[..DeviceId::SIZE])?;
+        Ok(DeviceId::SIZE)
+    }
+}
+
+impl Command for SetDeviceIdentifier {
+    const OPERATION: OperationId = OperationId::SetDeviceIdentifier;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::device_id::DeviceIdSource;
+
+    #[test]
+    fn pack() {
+        let command = SetDeviceIdentifier::new(DeviceId {
+            source: DeviceIdSource::BluetoothSig,
+            vendor: 0x0002,
+            product: 0x1234,
+            version: 0x0105,
+        });
+        let mut data = [0xffu8; 8];
+        assert_eq!(command.pack(&mut data).unwrap(), 8);
+        assert_eq!(data, [0x01, 0x00, 0x02, 0x00, 0x34, 0x12, 0x05, 0x01]);
+        assert!(command.pack(&mut data[..7]).is_err());
+        assert_eq!(
+            <SetDeviceIdentifier as Command>::OPERATION,
+            OperationId::SetDeviceIdentifier
+        );
+    }
+}
@@ -0,0 +1,314 @@
+use byteorder::{ByteOrder, LittleEndian};
+
+use crate::{
+    error::HciError,
+    error::HciErrorKind,
+    events::{DeviceFound, DeviceFoundFlags, DiscoveringType},
+    pack::{Pack, PackFixed, UnpackFixed},
+    socket::MGMT_MAX_PARAMETERS_SIZE,
+    AddressInfo, Error, OperationId, Uuid,
+};
+
+use super::Command;
+
+/// Creates a discovery command with the discovery type as parameter. The
+/// reply holds the same discovery type.
+macro_rules! discovery_command {
+    ( $(#[$meta:meta])* $name:ident ) => (
+        $(#[$meta])*
+        #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+        pub struct $name {
+            pub discovering_type: DiscoveringType,
+        }
+
+        impl $name {
+            /// Create a new command
+            pub fn new(discovering_type: DiscoveringType) -> $name {
+                $name { discovering_type }
+            }
+        }
+
+        impl Pack<$name, Error> for $name {
+            fn pack(&self, data: &mut [u8]) -> Result<usize, Error> {
+                if data.is_empty() {
+                    return Err(Error::from(HciError::new(HciErrorKind::InvalidLength)));
+                }
+                data[0] = self.discovering_type.bits();
+                Ok(1)
+            }
+        }
+
+        impl<'a> UnpackFixed<'a, DiscoveringType, Error> for $name {
+            fn unpack(data: &'a [u8]) -> Result<DiscoveringType, Error> {
+                if data.len() != 1 {
+                    return Err(Error::from(HciError::new(HciErrorKind::NotEnoughData)));
+                }
+                match DiscoveringType::from_bits(data[0]) {
+                    Some(v) => Ok(v),
+                    None => Err(Error::from(HciError::new(HciErrorKind::InvalidValue))),
+                }
+            }
+        }
+
+        impl Command for $name {
+            const OPERATION: OperationId = OperationId::$name;
+        }
+    );
+}
+
+discovery_command!(
+    /// Start discovery of devices
+    ///
+    /// The command fails with status `Busy` if discovery is already running,
+    /// `NotPowered` if the controller is powered off and `Rejected` if another
+    /// socket owns the discovery.
+    StartDiscovery
+);
+
+discovery_command!(
+    /// Stop an ongoing discovery
+    ///
+    /// The command fails with status `Rejected` if there is no ongoing
+    /// discovery started by this socket.
+    StopDiscovery
+);
+
+discovery_command!(
+    /// Start discovery of devices in limited discoverable mode
+    ///
+    /// Fails in the same way as `StartDiscovery`.
+    StartLimitedDiscovery
+);
+
+/// Start discovery of devices, only reporting devices matching the filter
+///
+/// Devices are reported if their RSSI is at or above the threshold and, if
+/// any UUIDs are given, they advertise one of the UUIDs. Fails in the same way
+/// as `StartDiscovery`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StartServiceDiscovery {
+    pub discovering_type: DiscoveringType,
+    /// RSSI threshold in dBm, `RSSI_THRESHOLD_DISABLED` disables the threshold
+    pub rssi_threshold: i8,
+    pub uuids: Vec<Uuid>,
+}
+
+impl StartServiceDiscovery {
+    /// RSSI threshold value disabling filtering on RSSI
+    pub const RSSI_THRESHOLD_DISABLED: i8 = 127;
+    /// Maximum number of UUIDs fitting in a command
+    pub const MAX_UUIDS: usize = (MGMT_MAX_PARAMETERS_SIZE - 4) / Uuid::SIZE;
+
+    /// Create a new command
+    pub fn new(
+        discovering_type: DiscoveringType,
+        rssi_threshold: i8,
+        uuids: Vec<Uuid>,
+    ) -> StartServiceDiscovery {
+        StartServiceDiscovery {
+            discovering_type,
+            rssi_threshold,
+            uuids,
+        }
+    }
+}
+
+impl Pack<StartServiceDiscovery, Error> for StartServiceDiscovery {
+    fn pack(&self, data: &mut [u8]) -> Result<usize, Error> {
+        if self.uuids.len() > StartServiceDiscovery::MAX_UUIDS {
+            return Err(Error::from(HciError::new(HciErrorKind::InvalidLength)));
+        }
+        let size = 4 + self.uuids.len() * Uuid::SIZE;
+        if data.len() < size {
+            return Err(Error::from(HciError::new(HciErrorKind::InvalidLength)));
+        }
+        data[0] = self.discovering_type.bits();
+        data[1] = self.rssi_threshold as u8;
+        LittleEndian::write_u16(&mut data[2..4], self.uuids.len() as u16);
+        for (uuid, chunk) in self
+            .uuids
+            .iter()
+            .zip(data[4..size].chunks_exact_mut(Uuid::SIZE))
+        {
+            uuid.pack(chunk)?;
+        }
+        Ok(size)
+    }
+}
+
+impl<'a> UnpackFixed<'a, DiscoveringType, Error> for StartServiceDiscovery {
+    fn unpack(data: &'a [u8]) -> Result<DiscoveringType, Error> {
+        StartDiscovery::unpack(data)
+    }
+}
+
+impl Command for StartServiceDiscovery {
+    const OPERATION: OperationId = OperationId::StartServiceDiscovery;
+}
+
+/// Confirm whether the name of a found device is known
+///
+/// Sent in response to a `DeviceFound` event with the confirm name flag set.
+/// If the name is not known the kernel will request the name from the
+/// device. The reply holds the address of the device.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ConfirmName {
+    pub address_info: AddressInfo,
+    pub name_known: bool,
+}
+
+impl ConfirmName {
+    /// Create a new command
+    pub fn new(address_info: AddressInfo, name_known: bool) -> ConfirmName {
+        ConfirmName {
+            address_info,
+            name_known,
+        }
+    }
+
+    /// Create a command answering a `DeviceFound` event
+    ///
+    /// Returns `None` if the event does not ask for a name confirmation. The
+    /// name is known if the EIR data holds the complete local name, otherwise
+    /// `name_cache` is asked whether the name of the device is known.
+    pub fn from_device_found<F>(
+        device_found: &DeviceFound,
+        name_cache: F,
+    ) -> Result<Option<ConfirmName>, Error>
+    where
+        F: FnOnce(&AddressInfo) -> bool,
+    {
+        if !device_found
+            .flags()
+            .contains(DeviceFoundFlags::CONFIRM_NAME)
+        {
+            return Ok(None);
+        }
+        let name_known =
+            device_found.has_complete_name()? || name_cache(&device_found.address_info);
+        Ok(Some(ConfirmName::new(
+            device_found.address_info,
+            name_known,
+        )))
+    }
+}
+
+impl Pack<ConfirmName, Error> for ConfirmName {
+    fn pack(&self, data: &mut [u8]) -> Result<usize, Error> {
+        if data.len() < AddressInfo::SIZE + 1 {
+            return Err(Error::from(HciError::new(HciErrorKind::InvalidLength)));
+        }
+        self.address_info.pack(&mut data[..AddressInfo::SIZE])?;
+        data[AddressInfo::SIZE] = u8::from(self.name_known);
+        Ok(AddressInfo::SIZE + 1)
+    }
+}
+
+impl<'a> UnpackFixed<'a, AddressInfo, Error> for ConfirmName {
+    fn unpack(data: &'a [u8]) -> Result<AddressInfo, Error> {
+        AddressInfo::unpack(data)
+    }
+}
+
+impl Command for ConfirmName {
+    const OPERATION: OperationId = OperationId::ConfirmName;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{events::CommandComplete, pack::Unpack, Status};
+
+    #[test]
+    fn pack_unpack() {
+        let discovering_type = DiscoveringType::LE_PUBLIC | DiscoveringType::LE_RANDOM;
+        let mut data = [0u8; 1];
+        assert_eq!(
+            StartDiscovery::new(discovering_type)
+                .pack(&mut data)
+                .unwrap(),
+            1
+        );
+        assert_eq!(data, [0x06]);
+        assert_eq!(StartDiscovery::unpack(&data).unwrap(), discovering_type);
+        assert!(StopDiscovery::unpack(&[0x08]).is_err());
+    }
+
+    #[test]
+    fn reply_status() {
+        let data = [0x23, 0x00, 0x0a, 0x07];
+        let (complete, _) = CommandComplete::unpack(&data).unwrap();
+        match complete.parameters() {
+            Err(Error::Status(Status::Busy)) => (),
+            result => panic!("Unexpected result {:?}", result),
+        }
+        let data = [0x41, 0x00, 0x00, 0x07];
+        let (complete, _) = CommandComplete::unpack(&data).unwrap();
+        assert_eq!(complete.operation, OperationId::StartLimitedDiscovery);
+        let parameters = complete.parameters().unwrap();
+        assert_eq!(
+            StartLimitedDiscovery::unpack(parameters).unwrap(),
+            DiscoveringType::all()
+        );
+    }
+
+    #[test]
+    fn service_discovery() {
+        let command = StartServiceDiscovery::new(
+            DiscoveringType::LE_PUBLIC | DiscoveringType::LE_RANDOM,
+            -70,
+            vec![
+                Uuid::from(0x180du16),
+                "6e400001-b5a3-f393-e0a9-e50e24dcca9e".parse().unwrap(),
+            ],
+        );
+        let mut data = [0u8; 36];
+        assert_eq!(command.pack(&mut data).unwrap(), 36);
+        assert_eq!(&data[..4], &[0x06, 0xba, 0x02, 0x00]);
+        assert_eq!(&data[4..20], &Uuid::from(0x180du16).to_bytes_le());
+        assert_eq!(&data[20..22], &[0x9e, 0xca]);
+        assert_eq!(&data[34..36], &[0x40, 0x6e]);
+
+        let command = StartServiceDiscovery::new(
+            DiscoveringType::BR_EDR,
+            StartServiceDiscovery::RSSI_THRESHOLD_DISABLED,
+            Vec::new(),
+        );
+        assert_eq!(command.pack(&mut data).unwrap(), 4);
+        assert_eq!(&data[..4], &[0x01, 0x7f, 0x00, 0x00]);
+    }
+
+    #[test]
+    fn confirm_name() {
+        let mut data = vec![
+            0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x00, 0xc4, 0x01, 0x00, 0x00, 0x00, 0x03, 0x00,
+            0x02, 0x01, 0x06,
+        ];
+        let (device_found, _) = DeviceFound::unpack(&data).unwrap();
+        let confirm = ConfirmName::from_device_found(&device_found, |_| false).unwrap();
+        let confirm = confirm.unwrap();
+        assert!(!confirm.name_known);
+        let confirm = ConfirmName::from_device_found(&device_found, |address_info| {
+            address_info.address.bytes() == [0x01, 0x02, 0x03, 0x04, 0x05, 0x06]
+        })
+        .unwrap();
+        assert!(confirm.unwrap().name_known);
+
+        let mut buffer = [0u8; 8];
+        assert_eq!(confirm.unwrap().pack(&mut buffer).unwrap(), 8);
+        assert_eq!(buffer[7], 0x01);
+
+        data[12] = 0x09;
+        data.extend_from_slice(&[0x05, 0x09, b'n', b'a', b'm', b'e']);
+        let (device_found, _) = DeviceFound::unpack(&data).unwrap();
+        let confirm = ConfirmName::from_device_found(&device_found, |_| false).unwrap();
+        assert!(confirm.unwrap().name_known);
+
+        data[8] = 0x00;
+        let (device_found, _) = DeviceFound::unpack(&data).unwrap();
+        assert_eq!(
+            ConfirmName::from_device_found(&device_found, |_| false).unwrap(),
+            None
+        );
+    }
+}
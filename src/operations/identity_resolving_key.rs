@@ -0,0 +1,139 @@
+use byteorder::{ByteOrder, LittleEndian};
+
+use crate::{
+    error::HciError,
+    error::HciErrorKind,
+    pack::{Pack, PackFixed},
+    socket::MGMT_MAX_PARAMETERS_SIZE,
+    AddressInfo, Error, OperationId,
+};
+
+use super::Command;
+
+/// Identity resolving key (IRK) of a device using LE privacy
+///
+/// The address must be the identity address of the device, either a public
+/// address or a static random address.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct IdentityResolvingKey {
+    pub address_info: AddressInfo,
+    pub value: [u8; 16],
+}
+
+impl IdentityResolvingKey {
+    /// Size of a packed identity resolving key in octets
+    pub const SIZE: usize = AddressInfo::SIZE + 16;
+}
+
+impl PackFixed<IdentityResolvingKey, Error> for IdentityResolvingKey {
+    fn pack(&self, data: &mut [u8]) -> Result<(), Error> {
+        if data.len() != IdentityResolvingKey::SIZE {
+            return Err(Error::from(HciError::new(HciErrorKind::InvalidLength)));
+        }
+        if !self.address_info.is_identity() {
+            return Err(Error::from(HciError::new(HciErrorKind::InvalidAddress)));
+        }
+        self.address_info.pack(&mut data[..AddressInfo::SIZE])?;
+        data[AddressInfo::SIZE..].copy_from_slice(&self.value);
+        Ok(())
+    }
+}
+
+/// Load the identity resolving keys of devices using LE privacy, replacing
+/// any keys known by the kernel
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LoadIrks {
+    pub keys: Vec<IdentityResolvingKey>,
+}
+
+impl LoadIrks {
+    /// Maximum number of keys fitting in a command
+    pub const MAX_KEYS: usize = (MGMT_MAX_PARAMETERS_SIZE - 2) / IdentityResolvingKey::SIZE;
+
+    /// Create a new command
+    pub fn new(keys: Vec<IdentityResolvingKey>) -> LoadIrks {
+        LoadIrks { keys }
+    }
+}
+
+impl Pack<LoadIrks, Error> for LoadIrks {
+    fn pack(&self, data: &mut [u8]) -> Result<usize, Error> {
+        if self.keys.len() > LoadIrks::MAX_KEYS {
+            return Err(Error::from(HciError::new(HciErrorKind::InvalidLength)));
+        }
+        let size = 2 + self.keys.len() * IdentityResolvingKey::SIZE;
+        if data.len() < size {
+            return Err(Error::from(HciError::new(HciErrorKind::InvalidLength)));
+        }
+        LittleEndian::write_u16(&mut data[0..2], self.keys.len() as u16);
+        for (key, chunk) in self
+            .keys
+            .iter()
+            .zip(data[2..size].chunks_exact_mut(IdentityResolvingKey::SIZE))
+        {
+            key.pack(chunk)?;
+        }
+        Ok(size)
+    }
+}
+
+impl Command for LoadIrks {
+    const OPERATION: OperationId = OperationId::LoadIrks;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AddressType, HardwareAddress};
+
+    #[test]
+    fn pack() {
+        let command = LoadIrks::new(vec![
+            IdentityResolvingKey {
+                address_info: AddressInfo {
+                    address: HardwareAddress::from([0x01, 0x02, 0x03, 0x04, 0x05, 0x06]),
+                    address_type: AddressType::LePublic,
+                },
+                value: [0x33; 16],
+            },
+            IdentityResolvingKey {
+                address_info: AddressInfo {
+                    address: HardwareAddress::from([0x01, 0x02, 0x03, 0x04, 0x05, 0xc6]),
+                    address_type: AddressType::LeRandom,
+                },
+                value: [0x33; 16],
+            },
+        ]);
+        let mut data = [0u8; 48];
+        assert_eq!(command.pack(&mut data).unwrap(), 48);
+        assert_eq!(&data[..2], &[0x02, 0x00]);
+        assert_eq!(&data[2..9], &[0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x01]);
+        assert_eq!(&data[9..25], &[0x33; 16]);
+        assert_eq!(&data[25..32], &[0x01, 0x02, 0x03, 0x04, 0x05, 0xc6, 0x02]);
+    }
+
+    #[test]
+    fn reject_non_identity_address() {
+        let mut data = [0u8; 25];
+        for (address_type, msb) in &[
+            (AddressType::BrEdr, 0x06),
+            (AddressType::LeRandom, 0x46),
+            (AddressType::LeRandom, 0x06),
+        ] {
+            let key = IdentityResolvingKey {
+                address_info: AddressInfo {
+                    address: HardwareAddress::from([0x01, 0x02, 0x03, 0x04, 0x05, *msb]),
+                    address_type: *address_type,
+                },
+                value: [0x33; 16],
+            };
+            let command = LoadIrks::new(vec![key]);
+            match command.pack(&mut data) {
+                Err(Error::Hci(HciError {
+                    kind: HciErrorKind::InvalidAddress,
+                })) => (),
+                result => panic!("Unexpected result {:?}", result),
+            }
+        }
+    }
+}
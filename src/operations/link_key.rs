@@ -0,0 +1,174 @@
+use std::convert::TryFrom;
+
+use byteorder::{ByteOrder, LittleEndian};
+
+use crate::{
+    address_info::AddressType,
+    error::HciError,
+    error::HciErrorKind,
+    pack::{Pack, PackFixed},
+    socket::MGMT_MAX_PARAMETERS_SIZE,
+    AddressInfo, Error, OperationId,
+};
+
+use super::Command;
+
+extended_enum!(
+    LinkKeyType, u8,
+    Combination => 0x00,
+    LocalUnit => 0x01,
+    RemoteUnit => 0x02,
+    DebugCombination => 0x03,
+    UnauthenticatedP192 => 0x04,
+    AuthenticatedP192 => 0x05,
+    ChangedCombination => 0x06,
+    UnauthenticatedP256 => 0x07,
+    AuthenticatedP256 => 0x08,
+);
+
+/// Maximum length of a PIN code
+const MAX_PIN_LENGTH: u8 = 16;
+
+/// BR/EDR link key for a bonded device
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct LinkKey {
+    pub address_info: AddressInfo,
+    pub key_type: LinkKeyType,
+    pub value: [u8; 16],
+    pub pin_length: u8,
+}
+
+impl LinkKey {
+    /// Size of a packed link key in octets
+    pub const SIZE: usize = AddressInfo::SIZE + 18;
+}
+
+impl PackFixed<LinkKey, Error> for LinkKey {
+    fn pack(&self, data: &mut [u8]) -> Result<(), Error> {
+        if data.len() != LinkKey::SIZE {
+            return Err(Error::from(HciError::new(HciErrorKind::InvalidLength)));
+        }
+        if self.address_info.address_type != AddressType::BrEdr || self.pin_length > MAX_PIN_LENGTH
+        {
+            return Err(Error::from(HciError::new(HciErrorKind::InvalidValue)));
+        }
+        let mut offset = AddressInfo::SIZE;
+        self.address_info.pack(&mut data[..offset])?;
+        data[offset] = u8::from(self.key_type);
+        offset += 1;
+        data[offset..offset + 16].copy_from_slice(&self.value);
+        offset += 16;
+        data[offset] = self.pin_length;
+        Ok(())
+    }
+}
+
+/// Load the link keys of bonded BR/EDR devices, replacing any keys known by
+/// the kernel
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LoadLinkKeys {
+    pub debug_keys: bool,
+    pub keys: Vec<LinkKey>,
+}
+
+impl LoadLinkKeys {
+    /// Maximum number of keys fitting in a command
+    pub const MAX_KEYS: usize = (MGMT_MAX_PARAMETERS_SIZE - 3) / LinkKey::SIZE;
+
+    /// Create a new command
+    pub fn new(debug_keys: bool, keys: Vec<LinkKey>) -> LoadLinkKeys {
+        LoadLinkKeys { debug_keys, keys }
+    }
+}
+
+impl Pack<LoadLinkKeys, Error> for LoadLinkKeys {
+    fn pack(&self, data: &mut [u8]) -> Result<usize, Error> {
+        if self.keys.len() > LoadLinkKeys::MAX_KEYS {
+            return Err(Error::from(HciError::new(HciErrorKind::InvalidLength)));
+        }
+        let size = 3 + self.keys.len() * LinkKey::SIZE;
+        if data.len() < size {
+            return Err(Error::from(HciError::new(HciErrorKind::InvalidLength)));
+        }
+        data[0] = u8::from(self.debug_keys);
+        LittleEndian::write_u16(&mut data[1..3], self.keys.len() as u16);
+        for (key, chunk) in self
+            .keys
+            .iter()
+            .zip(data[3..size].chunks_exact_mut(LinkKey::SIZE))
+        {
+            key.pack(chunk)?;
+        }
+        Ok(size)
+    }
+}
+
+impl Command for LoadLinkKeys {
+    const OPERATION: OperationId = OperationId::LoadLinkKeys;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::HardwareAddress;
+
+    #[test]
+    fn pack() {
+        let key = LinkKey {
+            address_info: AddressInfo {
+                address: HardwareAddress::from([0x01, 0x02, 0x03, 0x04, 0x05, 0x06]),
+                address_type: AddressType::BrEdr,
+            },
+            key_type: LinkKeyType::AuthenticatedP256,
+            value: [0xaa; 16],
+            pin_length: 0,
+        };
+        let command = LoadLinkKeys::new(false, vec![key; 2]);
+        let mut data = [0u8; 53];
+        assert_eq!(command.pack(&mut data).unwrap(), 53);
+        assert_eq!(&data[..3], &[0x00, 0x02, 0x00]);
+        assert_eq!(
+            &data[3..11],
+            &[0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x00, 0x08]
+        );
+        assert_eq!(&data[11..27], &[0xaa; 16]);
+        assert_eq!(data[27], 0);
+        assert_eq!(&data[28..36], &data[3..11]);
+
+        assert!(command.pack(&mut data[..52]).is_err());
+
+        let key = LinkKey {
+            address_info: AddressInfo {
+                address: HardwareAddress::from([0x01, 0x02, 0x03, 0x04, 0x05, 0x06]),
+                address_type: AddressType::LePublic,
+            },
+            key_type: LinkKeyType::AuthenticatedP256,
+            value: [0xaa; 16],
+            pin_length: 0,
+        };
+        let command = LoadLinkKeys::new(true, vec![key]);
+        assert!(command.pack(&mut data).is_err());
+    }
+
+    #[test]
+    fn pack_many() {
+        let key = LinkKey {
+            address_info: AddressInfo {
+                address: HardwareAddress::from([0x01, 0x02, 0x03, 0x04, 0x05, 0x06]),
+                address_type: AddressType::BrEdr,
+            },
+            key_type: LinkKeyType::AuthenticatedP256,
+            value: [0xaa; 16],
+            pin_length: 0,
+        };
+        let command = LoadLinkKeys::new(false, vec![key; LoadLinkKeys::MAX_KEYS]);
+        let mut data = vec![0u8; MGMT_MAX_PARAMETERS_SIZE];
+        assert_eq!(
+            command.pack(&mut data).unwrap(),
+            3 + LoadLinkKeys::MAX_KEYS * LinkKey::SIZE
+        );
+
+        let command = LoadLinkKeys::new(false, vec![key; LoadLinkKeys::MAX_KEYS + 1]);
+        assert!(command.pack(&mut data).is_err());
+    }
+}
@@ -0,0 +1,176 @@
+use crate::{
+    error::HciError,
+    error::HciErrorKind,
+    pack::{Pack, UnpackFixed},
+    Error, OperationId,
+};
+
+use super::Command;
+
+/// Maximum length of the local name in octets, excluding termination
+pub const MAX_NAME_LENGTH: usize = 248;
+/// Maximum length of the short local name in octets, excluding termination
+pub const MAX_SHORT_NAME_LENGTH: usize = 10;
+
+const NAME_SIZE: usize = MAX_NAME_LENGTH + 1;
+const SHORT_NAME_SIZE: usize = MAX_SHORT_NAME_LENGTH + 1;
+const LOCAL_NAME_SIZE: usize = NAME_SIZE + SHORT_NAME_SIZE;
+
+/// Truncate a string to at most `length` octets without splitting a
+/// character
+fn truncate(value: &str, length: usize) -> &str {
+    if value.len() <= length {
+        return value;
+    }
+    let mut end = length;
+    while !value.is_char_boundary(end) {
+        end -= 1;
+    }
+    &value[..end]
+}
+
+/// Unpack a null terminated string from a fixed size field
+///
+/// The kernel stores the name as raw octets, invalid UTF-8 sequences, for
+/// example from a name truncated by another tool, are replaced.
+fn unpack_name(data: &[u8]) -> String {
+    let end = data.iter().position(|b| *b == 0).unwrap_or(data.len());
+    String::from_utf8_lossy(&data[..end]).into_owned()
+}
+
+/// Set the local name and short local name of a controller
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SetLocalName {
+    name: String,
+    short_name: String,
+}
+
+impl SetLocalName {
+    /// Create a new command
+    ///
+    /// The names are truncated on character boundaries to fit the command.
+    /// If no short name is given, one is derived from the name.
+    pub fn new(name: &str, short_name: Option<&str>) -> SetLocalName {
+        let name = truncate(name, MAX_NAME_LENGTH);
+        let short_name = truncate(short_name.unwrap_or(name), MAX_SHORT_NAME_LENGTH);
+        SetLocalName {
+            name: name.to_string(),
+            short_name: short_name.to_string(),
+        }
+    }
+
+    /// Local name to set
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Short local name to set
+    pub fn short_name(&self) -> &str {
+        &self.short_name
+    }
+}
+
+impl Pack<SetLocalName, Error> for SetLocalName {
+    fn pack(&self, data: &mut [u8]) -> Result<usize, Error> {
+        if data.len() < LOCAL_NAME_SIZE {
+            return Err(Error::from(HciError::new(HciErrorKind::InvalidLength)));
+        }
+        for b in data[..LOCAL_NAME_SIZE].iter_mut() {
+            *b = 0;
+        }
+        data[..self.name.len()].copy_from_slice(self.name.as_bytes());
+        let end = NAME_SIZE + self.short_name.len();
+        data[NAME_SIZE..end].copy_from_slice(self.short_name.as_bytes());
+        Ok(LOCAL_NAME_SIZE)
+    }
+}
+
+impl<'a> UnpackFixed<'a, LocalName, Error> for SetLocalName {
+    fn unpack(data: &'a [u8]) -> Result<LocalName, Error> {
+        LocalName::unpack(data)
+    }
+}
+
+impl Command for SetLocalName {
+    const OPERATION: OperationId = OperationId::SetLocalName;
+}
+
+/// Local name and short local name of a controller
+///
+/// Returned by the SetLocalName command and the LocalNameChanged event.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LocalName {
+    pub name: String,
+    pub short_name: String,
+}
+
+impl LocalName {
+    /// Size of the packed local name in octets
+    pub const SIZE: usize = LOCAL_NAME_SIZE;
+}
+
+impl<'a> UnpackFixed<'a, LocalName, Error> for LocalName {
+    fn unpack(data: &'a [u8]) -> Result<LocalName, Error> {
+        if data.len() != LOCAL_NAME_SIZE {
+            return Err(Error::from(HciError::new(HciErrorKind::NotEnoughData)));
+        }
+        let name = unpack_name(&data[..NAME_SIZE]);
+        let short_name = unpack_name(&data[NAME_SIZE..]);
+        Ok(LocalName { name, short_name })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn derive_short_name() {
+        let command = SetLocalName::new("Kiosk terminal", None);
+        assert_eq!(command.name(), "Kiosk terminal");
+        assert_eq!(command.short_name(), "Kiosk term");
+
+        let command = SetLocalName::new("Kiosk terminal", Some("Kiosk"));
+        assert_eq!(command.short_name(), "Kiosk");
+    }
+
+    #[test]
+    fn truncate_on_character_boundary() {
+        // 'ö' is two octets, the tenth octet is in the middle of the fifth 'ö'
+        let command = SetLocalName::new("öööööö", None);
+        assert_eq!(command.name(), "öööööö");
+        assert_eq!(command.short_name(), "ööööö");
+
+        let name = "€".repeat(100);
+        let command = SetLocalName::new(&name, None);
+        assert_eq!(command.name().len(), 246);
+        assert_eq!(command.short_name(), "€€€");
+    }
+
+    #[test]
+    fn pack_unpack() {
+        let command = SetLocalName::new("Kiosk terminal", None);
+        let mut buffer = [0xffu8; LocalName::SIZE];
+        assert_eq!(command.pack(&mut buffer).unwrap(), LocalName::SIZE);
+        assert_eq!(&buffer[..15], b"Kiosk terminal\0");
+        assert_eq!(&buffer[249..260], b"Kiosk term\0");
+
+        let local_name = SetLocalName::unpack(&buffer).unwrap();
+        assert_eq!(local_name.name, "Kiosk terminal");
+        assert_eq!(local_name.short_name, "Kiosk term");
+
+        assert!(command.pack(&mut buffer[..259]).is_err());
+        assert!(LocalName::unpack(&buffer[..259]).is_err());
+    }
+
+    #[test]
+    fn unpack_invalid_utf8() {
+        let mut buffer = [0u8; LocalName::SIZE];
+        // "Kiosk ö" truncated in the middle of 'ö'
+        buffer[..7].copy_from_slice(b"Kiosk \xc3");
+        buffer[249..254].copy_from_slice(b"Kiosk");
+        let local_name = LocalName::unpack(&buffer).unwrap();
+        assert_eq!(local_name.name, "Kiosk \u{fffd}");
+        assert_eq!(local_name.short_name, "Kiosk");
+    }
+}
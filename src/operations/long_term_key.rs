@@ -0,0 +1,174 @@
+use std::convert::TryFrom;
+
+use byteorder::{ByteOrder, LittleEndian};
+
+use crate::{
+    error::HciError,
+    error::HciErrorKind,
+    pack::{Pack, PackFixed},
+    socket::MGMT_MAX_PARAMETERS_SIZE,
+    AddressInfo, Error, OperationId,
+};
+
+use super::Command;
+
+extended_enum!(
+    LongTermKeyType, u8,
+    Unauthenticated => 0x00,
+    Authenticated => 0x01,
+    P256Unauthenticated => 0x02,
+    P256Authenticated => 0x03,
+    P256Debug => 0x04,
+);
+
+/// LE long term key (LTK) for a bonded device
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct LongTermKey {
+    pub address_info: AddressInfo,
+    pub key_type: LongTermKeyType,
+    /// The key is used when the local device is the initiator (central)
+    pub initiator: bool,
+    /// Encryption key size, 7 to 16 octets
+    pub encryption_size: u8,
+    /// Encrypted diversifier
+    pub ediv: u16,
+    /// Random number
+    pub rand: u64,
+    pub value: [u8; 16],
+}
+
+impl LongTermKey {
+    /// Size of a packed long term key in octets
+    pub const SIZE: usize = AddressInfo::SIZE + 29;
+}
+
+impl PackFixed<LongTermKey, Error> for LongTermKey {
+    fn pack(&self, data: &mut [u8]) -> Result<(), Error> {
+        if data.len() != LongTermKey::SIZE {
+            return Err(Error::from(HciError::new(HciErrorKind::InvalidLength)));
+        }
+        if !self.address_info.is_identity() {
+            return Err(Error::from(HciError::new(HciErrorKind::InvalidAddress)));
+        }
+        if !(7..=16).contains(&self.encryption_size) {
+            return Err(Error::from(HciError::new(HciErrorKind::InvalidValue)));
+        }
+        let mut offset = AddressInfo::SIZE;
+        self.address_info.pack(&mut data[..offset])?;
+        data[offset] = u8::from(self.key_type);
+        offset += 1;
+        data[offset] = u8::from(self.initiator);
+        offset += 1;
+        data[offset] = self.encryption_size;
+        offset += 1;
+        LittleEndian::write_u16(&mut data[offset..offset + 2], self.ediv);
+        offset += 2;
+        LittleEndian::write_u64(&mut data[offset..offset + 8], self.rand);
+        offset += 8;
+        data[offset..offset + 16].copy_from_slice(&self.value);
+        Ok(())
+    }
+}
+
+/// Load the long term keys of bonded LE devices, replacing any keys known by
+/// the kernel
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LoadLongTermKeys {
+    pub keys: Vec<LongTermKey>,
+}
+
+impl LoadLongTermKeys {
+    /// Maximum number of keys fitting in a command
+    pub const MAX_KEYS: usize = (MGMT_MAX_PARAMETERS_SIZE - 2) / LongTermKey::SIZE;
+
+    /// Create a new command
+    pub fn new(keys: Vec<LongTermKey>) -> LoadLongTermKeys {
+        LoadLongTermKeys { keys }
+    }
+}
+
+impl Pack<LoadLongTermKeys, Error> for LoadLongTermKeys {
+    fn pack(&self, data: &mut [u8]) -> Result<usize, Error> {
+        if self.keys.len() > LoadLongTermKeys::MAX_KEYS {
+            return Err(Error::from(HciError::new(HciErrorKind::InvalidLength)));
+        }
+        let size = 2 + self.keys.len() * LongTermKey::SIZE;
+        if data.len() < size {
+            return Err(Error::from(HciError::new(HciErrorKind::InvalidLength)));
+        }
+        LittleEndian::write_u16(&mut data[0..2], self.keys.len() as u16);
+        for (key, chunk) in self
+            .keys
+            .iter()
+            .zip(data[2..size].chunks_exact_mut(LongTermKey::SIZE))
+        {
+            key.pack(chunk)?;
+        }
+        Ok(size)
+    }
+}
+
+impl Command for LoadLongTermKeys {
+    const OPERATION: OperationId = OperationId::LoadLongTermKeys;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AddressType, HardwareAddress};
+
+    #[test]
+    fn pack() {
+        let key = LongTermKey {
+            address_info: AddressInfo {
+                address: HardwareAddress::from([0x01, 0x02, 0x03, 0x04, 0x05, 0xc6]),
+                address_type: AddressType::LePublic,
+            },
+            key_type: LongTermKeyType::P256Authenticated,
+            initiator: true,
+            encryption_size: 16,
+            ediv: 0x1234,
+            rand: 0x0102_0304_0506_0708,
+            value: [0x55; 16],
+        };
+        let command = LoadLongTermKeys::new(vec![key]);
+        let mut data = [0u8; 38];
+        assert_eq!(command.pack(&mut data).unwrap(), 38);
+        assert_eq!(&data[..2], &[0x01, 0x00]);
+        assert_eq!(
+            &data[2..22],
+            &[
+                0x01, 0x02, 0x03, 0x04, 0x05, 0xc6, 0x01, 0x03, 0x01, 0x10, 0x34, 0x12, 0x08, 0x07,
+                0x06, 0x05, 0x04, 0x03, 0x02, 0x01
+            ]
+        );
+        assert_eq!(&data[22..38], &[0x55; 16]);
+        assert!(command.pack(&mut data[..37]).is_err());
+    }
+
+    #[test]
+    fn validate() {
+        let mut data = [0u8; 38];
+        for (address_type, encryption_size, valid) in &[
+            (AddressType::LeRandom, 7, true),
+            (AddressType::LePublic, 6, false),
+            (AddressType::LePublic, 17, false),
+            (AddressType::BrEdr, 16, false),
+        ] {
+            let key = LongTermKey {
+                address_info: AddressInfo {
+                    address: HardwareAddress::from([0x01, 0x02, 0x03, 0x04, 0x05, 0xc6]),
+                    address_type: *address_type,
+                },
+                key_type: LongTermKeyType::P256Authenticated,
+                initiator: true,
+                encryption_size: *encryption_size,
+                ediv: 0x1234,
+                rand: 0x0102_0304_0506_0708,
+                value: [0x55; 16],
+            };
+            let command = LoadLongTermKeys::new(vec![key]);
+            assert_eq!(command.pack(&mut data).is_ok(), *valid);
+        }
+    }
+}
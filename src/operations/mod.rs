@@ -1,3 +1,71 @@
+#[macro_use]
+mod address_command;
+
+mod advertising;
+mod appearance;
+mod block;
+mod class_of_device;
+mod clock;
+mod configuration;
+mod connection;
+mod connection_parameters;
+mod device;
+mod device_id;
+mod discovery;
+mod identity_resolving_key;
+mod link_key;
+mod local_name;
+mod long_term_key;
+mod out_of_band;
+mod pairing;
+mod pairing_reply;
+mod privacy;
+mod scan_parameters;
+mod static_address;
+mod uuid;
+
+use std::time::Duration;
+
+use crate::{pack::Pack, Error};
+
+pub use advertising::{
+    AddAdvertising, AdvertisingFeatures, AdvertisingFlags, AdvertisingSizeInformation,
+    GetAdvertisingSizeInformation, ReadAdvancedFeatures, RemoveAdvertising,
+};
+pub use appearance::SetAppearance;
+pub use block::{BlockDevice, UnblockDevice};
+pub use class_of_device::SetDeviceClass;
+pub use clock::{Clock, ClockInformation, GetClockInformation};
+pub use configuration::{
+    ConfigurationInformation, ConfigurationOptions, ReadConfigurationInformation,
+    SetExternalConfiguration, SetPublicAddress,
+};
+pub use connection::{ConnectionInformation, Disconnect, GetConnectionInformation, GetConnections};
+pub use connection_parameters::{ConnectionParameters, LoadConnectionParameters};
+pub use device::{AddDevice, DeviceAction, RemoveDevice};
+pub use device_id::SetDeviceIdentifier;
+pub use discovery::{
+    ConfirmName, StartDiscovery, StartLimitedDiscovery, StartServiceDiscovery, StopDiscovery,
+};
+pub use identity_resolving_key::{IdentityResolvingKey, LoadIrks};
+pub use link_key::{LinkKey, LinkKeyType, LoadLinkKeys};
+pub use local_name::{LocalName, SetLocalName};
+pub use long_term_key::{LoadLongTermKeys, LongTermKey, LongTermKeyType};
+pub use out_of_band::{
+    AddRemoteOutOfBandData, LocalOutOfBandData, LocalOutOfBandExternalData, OutOfBandHash,
+    OutOfBandRandomizer, OutOfBandValues, ReadLocalOutOfBandData, ReadLocalOutOfBandExternalData,
+    RemoveRemoteOutOfBandData,
+};
+pub use pairing::{CancelPairDevice, IoCapability, PairDevice, SetIoCapability, UnpairDevice};
+pub use pairing_reply::{
+    PinCodeNegativeReply, PinCodeReply, UserConfirmNegativeReply, UserConfirmReply,
+    UserPasskeyNegativeReply, UserPasskeyReply,
+};
+pub use privacy::{Privacy, SetPrivacy};
+pub use scan_parameters::SetScanParameters;
+pub use static_address::SetStaticAddress;
+pub use uuid::{AddUuid, RemoveUuid};
+
 extended_enum_other!(OperationId, u16,
     ReadVersion => 0x0001,
     ReadCommands => 0x0002,
@@ -69,3 +137,20 @@ extended_enum_other!(OperationId, u16,
     GetPhyConfiguration => 0x0044,
     SetPhyConfiguration => 0x0045,
 );
+
+/// Management command with typed parameters
+pub trait Command: Pack<Self, Error> + Sized {
+    /// Operation used when sending the command
+    const OPERATION: OperationId;
+}
+
+/// Convert a duration to the nearest number of time units of the given length
+fn duration_to_units(duration: Duration, unit_nanos: u64) -> u128 {
+    let unit_nanos = u128::from(unit_nanos);
+    (duration.as_nanos() + unit_nanos / 2) / unit_nanos
+}
+
+/// Convert a number of time units of the given length to a duration
+fn units_to_duration(units: u64, unit_nanos: u64) -> Duration {
+    Duration::from_nanos(units * unit_nanos)
+}
@@ -0,0 +1,332 @@
+use byteorder::{ByteOrder, LittleEndian};
+
+use crate::{
+    eir::EirEntry,
+    error::HciError,
+    error::HciErrorKind,
+    pack::{Pack, PackFixed, Unpack, UnpackFixed},
+    AddressInfo, AddressType, AddressTypes, Error, OperationId,
+};
+
+use super::Command;
+
+/// Simple pairing hash C
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub struct OutOfBandHash(pub [u8; 16]);
+
+/// Simple pairing randomizer R
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub struct OutOfBandRandomizer(pub [u8; 16]);
+
+/// Hash and randomizer pair exchanged out of band
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub struct OutOfBandValues {
+    pub hash: OutOfBandHash,
+    pub randomizer: OutOfBandRandomizer,
+}
+
+impl OutOfBandValues {
+    /// Size of packed values in octets
+    pub const SIZE: usize = 32;
+}
+
+impl PackFixed<OutOfBandValues, Error> for OutOfBandValues {
+    fn pack(&self, data: &mut [u8]) -> Result<(), Error> {
+        if data.len() != OutOfBandValues::SIZE {
+            return Err(Error::from(HciError::new(HciErrorKind::InvalidLength)));
+        }
+        data[..16].copy_from_slice(&self.hash.0);
+        data[16..].copy_from_slice(&self.randomizer.0);
+        Ok(())
+    }
+}
+
+impl<'a> UnpackFixed<'a, OutOfBandValues, Error> for OutOfBandValues {
+    fn unpack(data: &'a [u8]) -> Result<OutOfBandValues, Error> {
+        if data.len() != OutOfBandValues::SIZE {
+            return Err(Error::from(HciError::new(HciErrorKind::NotEnoughData)));
+        }
+        let mut values = OutOfBandValues::default();
+        values.hash.0.copy_from_slice(&data[..16]);
+        values.randomizer.0.copy_from_slice(&data[16..]);
+        Ok(values)
+    }
+}
+
+/// Read the local out of band data used for pairing
+///
+/// The reply is a `LocalOutOfBandData`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ReadLocalOutOfBandData;
+
+impl Pack<ReadLocalOutOfBandData, Error> for ReadLocalOutOfBandData {
+    fn pack(&self, _data: &mut [u8]) -> Result<usize, Error> {
+        Ok(0)
+    }
+}
+
+impl<'a> UnpackFixed<'a, LocalOutOfBandData, Error> for ReadLocalOutOfBandData {
+    fn unpack(data: &'a [u8]) -> Result<LocalOutOfBandData, Error> {
+        LocalOutOfBandData::unpack(data)
+    }
+}
+
+impl Command for ReadLocalOutOfBandData {
+    const OPERATION: OperationId = OperationId::ReadLocalOutOfBandData;
+}
+
+/// Local out of band data
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct LocalOutOfBandData {
+    pub p192: OutOfBandValues,
+    /// Only available when secure connections are enabled
+    pub p256: Option<OutOfBandValues>,
+}
+
+impl<'a> UnpackFixed<'a, LocalOutOfBandData, Error> for LocalOutOfBandData {
+    fn unpack(data: &'a [u8]) -> Result<LocalOutOfBandData, Error> {
+        let p256 = match data.len() {
+            32 => None,
+            64 => Some(OutOfBandValues::unpack(&data[32..])?),
+            _ => return Err(Error::from(HciError::new(HciErrorKind::InvalidLength))),
+        };
+        let p192 = OutOfBandValues::unpack(&data[..32])?;
+        Ok(LocalOutOfBandData { p192, p256 })
+    }
+}
+
+/// Add out of band data received from a remote device
+///
+/// Without P-256 values only the P-192 values are sent, which is only valid
+/// for BR/EDR devices. LE devices only take P-256 values. Packing fails if
+/// neither is given or the values do not match the address type. The reply
+/// holds the address of the device.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct AddRemoteOutOfBandData {
+    pub address_info: AddressInfo,
+    pub p192: Option<OutOfBandValues>,
+    pub p256: Option<OutOfBandValues>,
+}
+
+impl AddRemoteOutOfBandData {
+    /// Create a new command
+    pub fn new(
+        address_info: AddressInfo,
+        p192: Option<OutOfBandValues>,
+        p256: Option<OutOfBandValues>,
+    ) -> AddRemoteOutOfBandData {
+        AddRemoteOutOfBandData {
+            address_info,
+            p192,
+            p256,
+        }
+    }
+}
+
+impl Pack<AddRemoteOutOfBandData, Error> for AddRemoteOutOfBandData {
+    fn pack(&self, data: &mut [u8]) -> Result<usize, Error> {
+        if self.p192.is_none() && self.p256.is_none() {
+            return Err(Error::from(HciError::new(HciErrorKind::InvalidValue)));
+        }
+        if self.address_info.address_type != AddressType::BrEdr
+            && (self.p192.is_some() || self.p256.is_none())
+        {
+            return Err(Error::from(HciError::new(HciErrorKind::InvalidValue)));
+        }
+        let size = match self.p256 {
+            Some(_) => AddressInfo::SIZE + 2 * OutOfBandValues::SIZE,
+            None => AddressInfo::SIZE + OutOfBandValues::SIZE,
+        };
+        if data.len() < size {
+            return Err(Error::from(HciError::new(HciErrorKind::InvalidLength)));
+        }
+        let mut offset = AddressInfo::SIZE;
+        self.address_info.pack(&mut data[..offset])?;
+        // Zeroed values are ignored by the kernel
+        let p192 = self.p192.unwrap_or_default();
+        p192.pack(&mut data[offset..offset + OutOfBandValues::SIZE])?;
+        offset += OutOfBandValues::SIZE;
+        if let Some(p256) = self.p256 {
+            p256.pack(&mut data[offset..offset + OutOfBandValues::SIZE])?;
+        }
+        Ok(size)
+    }
+}
+
+impl<'a> UnpackFixed<'a, AddressInfo, Error> for AddRemoteOutOfBandData {
+    fn unpack(data: &'a [u8]) -> Result<AddressInfo, Error> {
+        AddressInfo::unpack(data)
+    }
+}
+
+impl Command for AddRemoteOutOfBandData {
+    const OPERATION: OperationId = OperationId::AddRemoteOutOfBandData;
+}
+
+address_command!(
+    /// Remove out of band data of a remote device
+    ///
+    /// The reply holds the address of the device.
+    RemoveRemoteOutOfBandData
+);
+
+/// Read the local out of band data for the given address types, formatted as
+/// extended inquiry response data
+///
+/// The reply is a `LocalOutOfBandExternalData`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ReadLocalOutOfBandExternalData {
+    pub address_types: AddressTypes,
+}
+
+impl ReadLocalOutOfBandExternalData {
+    /// Create a new command
+    pub fn new(address_types: AddressTypes) -> ReadLocalOutOfBandExternalData {
+        ReadLocalOutOfBandExternalData { address_types }
+    }
+}
+
+impl Pack<ReadLocalOutOfBandExternalData, Error> for ReadLocalOutOfBandExternalData {
+    fn pack(&self, data: &mut [u8]) -> Result<usize, Error> {
+        if data.is_empty() {
+            return Err(Error::from(HciError::new(HciErrorKind::InvalidLength)));
+        }
+        data[0] = self.address_types.bits();
+        Ok(1)
+    }
+}
+
+impl<'a> UnpackFixed<'a, LocalOutOfBandExternalData<'a>, Error> for ReadLocalOutOfBandExternalData {
+    fn unpack(data: &'a [u8]) -> Result<LocalOutOfBandExternalData<'a>, Error> {
+        let (external, _) = LocalOutOfBandExternalData::unpack(data)?;
+        Ok(external)
+    }
+}
+
+impl Command for ReadLocalOutOfBandExternalData {
+    const OPERATION: OperationId = OperationId::ReadLocalOutOfBandExternalData;
+}
+
+/// Local out of band data formatted as extended inquiry response data
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LocalOutOfBandExternalData<'a> {
+    pub address_types: AddressTypes,
+    pub eir: Vec<EirEntry<'a>>,
+}
+
+impl<'a> Unpack<'a, LocalOutOfBandExternalData<'a>, Error> for LocalOutOfBandExternalData<'a> {
+    fn unpack(data: &'a [u8]) -> Result<(LocalOutOfBandExternalData<'a>, usize), Error> {
+        if data.len() < 3 {
+            return Err(Error::from(HciError::new(HciErrorKind::NotEnoughData)));
+        }
+        let address_types = match AddressTypes::from_bits(data[0]) {
+            Some(v) => v,
+            None => return Err(Error::from(HciError::new(HciErrorKind::InvalidValue))),
+        };
+        let length = LittleEndian::read_u16(&data[1..3]) as usize;
+        let end = 3 + length;
+        if end > data.len() {
+            return Err(Error::from(HciError::new(HciErrorKind::NotEnoughData)));
+        }
+        let eir = EirEntry::unpack_all(&data[3..end])?;
+        Ok((LocalOutOfBandExternalData { address_types, eir }, end))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{eir::DataType, HardwareAddress};
+
+    #[test]
+    fn local_out_of_band_data() {
+        let mut data = [0x11u8; 64];
+        for b in data[16..32].iter_mut() {
+            *b = 0x22;
+        }
+        let local = LocalOutOfBandData::unpack(&data[..32]).unwrap();
+        assert_eq!(local.p192.hash, OutOfBandHash([0x11; 16]));
+        assert_eq!(local.p192.randomizer, OutOfBandRandomizer([0x22; 16]));
+        assert_eq!(local.p256, None);
+        let local = LocalOutOfBandData::unpack(&data).unwrap();
+        assert!(local.p256.is_some());
+        assert_eq!(ReadLocalOutOfBandData::unpack(&data).unwrap(), local);
+        assert!(LocalOutOfBandData::unpack(&data[..48]).is_err());
+    }
+
+    #[test]
+    fn add_remote_out_of_band_data() {
+        let address_info = AddressInfo {
+            address: HardwareAddress::from([0x01, 0x02, 0x03, 0x04, 0x05, 0x06]),
+            address_type: AddressType::BrEdr,
+        };
+        let values = OutOfBandValues {
+            hash: OutOfBandHash([0x11; 16]),
+            randomizer: OutOfBandRandomizer([0x22; 16]),
+        };
+        let mut data = [0xffu8; 71];
+        let command = AddRemoteOutOfBandData::new(address_info, None, Some(values));
+        assert_eq!(command.pack(&mut data).unwrap(), 71);
+        assert_eq!(&data[7..39], &[0u8; 32]);
+        assert_eq!(&data[39..55], &[0x11; 16]);
+        assert_eq!(&data[55..71], &[0x22; 16]);
+
+        let command = AddRemoteOutOfBandData::new(address_info, Some(values), None);
+        assert_eq!(command.pack(&mut data).unwrap(), 39);
+        assert_eq!(&data[7..23], &[0x11; 16]);
+
+        let command = AddRemoteOutOfBandData::new(address_info, None, None);
+        assert!(command.pack(&mut data).is_err());
+    }
+
+    #[test]
+    fn add_remote_out_of_band_data_le() {
+        let address_info = AddressInfo {
+            address: HardwareAddress::from([0x01, 0x02, 0x03, 0x04, 0x05, 0x06]),
+            address_type: AddressType::LePublic,
+        };
+        let values = OutOfBandValues {
+            hash: OutOfBandHash([0x11; 16]),
+            randomizer: OutOfBandRandomizer([0x22; 16]),
+        };
+        let mut data = [0xffu8; 71];
+        let command = AddRemoteOutOfBandData::new(address_info, None, Some(values));
+        assert_eq!(command.pack(&mut data).unwrap(), 71);
+        assert_eq!(&data[7..39], &[0u8; 32]);
+
+        for (p192, p256) in &[
+            (Some(values), None),
+            (Some(values), Some(values)),
+            (None, None),
+        ] {
+            let command = AddRemoteOutOfBandData::new(address_info, *p192, *p256);
+            match command.pack(&mut data) {
+                Err(Error::Hci(HciError {
+                    kind: HciErrorKind::InvalidValue,
+                })) => (),
+                result => panic!("Unexpected result {:?}", result),
+            }
+        }
+    }
+
+    #[test]
+    fn local_out_of_band_external_data() {
+        let data = [
+            0x06, 0x0d, 0x00, 0x08, 0x1b, 0x01, 0x02, 0x03, 0x04, 0x05, 0xc6, 0x01, 0x02, 0x1c,
+            0x00, 0x00,
+        ];
+        let (external, used) = LocalOutOfBandExternalData::unpack(&data).unwrap();
+        assert_eq!(used, 16);
+        assert_eq!(external.address_types, AddressTypes::LE);
+        assert_eq!(external.eir.len(), 2);
+        assert_eq!(
+            external.eir[0].data_type,
+            DataType::LowEnergyBluetoothDeviceAddress
+        );
+        assert_eq!(external.eir[1].data, &[0x00]);
+        assert_eq!(
+            ReadLocalOutOfBandExternalData::unpack(&data).unwrap(),
+            external
+        );
+    }
+}
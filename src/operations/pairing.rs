@@ -0,0 +1,175 @@
+use std::convert::TryFrom;
+
+use crate::{
+    error::HciError,
+    error::HciErrorKind,
+    pack::{Pack, PackFixed, UnpackFixed},
+    AddressInfo, Error, OperationId,
+};
+
+use super::Command;
+
+extended_enum!(
+    IoCapability, u8,
+    DisplayOnly => 0x00,
+    DisplayYesNo => 0x01,
+    KeyboardOnly => 0x02,
+    NoInputNoOutput => 0x03,
+    KeyboardDisplay => 0x04,
+);
+
+/// Set the input and output capabilities used when pairing
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SetIoCapability {
+    pub io_capability: IoCapability,
+}
+
+impl SetIoCapability {
+    /// Create a new command
+    pub fn new(io_capability: IoCapability) -> SetIoCapability {
+        SetIoCapability { io_capability }
+    }
+}
+
+impl Pack<SetIoCapability, Error> for SetIoCapability {
+    fn pack(&self, data: &mut [u8]) -> Result<usize, Error> {
+        if data.is_empty() {
+            return Err(Error::from(HciError::new(HciErrorKind::InvalidLength)));
+        }
+        data[0] = u8::from(self.io_capability);
+        Ok(1)
+    }
+}
+
+impl Command for SetIoCapability {
+    const OPERATION: OperationId = OperationId::SetIoCapability;
+}
+
+/// Pair with a device
+///
+/// The reply holds the address of the device. The command completes when
+/// pairing has finished, a failed pairing is reported through the status of
+/// the reply, for example `AuthenticationFailed` or `AlreadyPaired`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct PairDevice {
+    pub address_info: AddressInfo,
+    pub io_capability: IoCapability,
+}
+
+impl PairDevice {
+    /// Create a new command
+    pub fn new(address_info: AddressInfo, io_capability: IoCapability) -> PairDevice {
+        PairDevice {
+            address_info,
+            io_capability,
+        }
+    }
+}
+
+impl Pack<PairDevice, Error> for PairDevice {
+    fn pack(&self, data: &mut [u8]) -> Result<usize, Error> {
+        if data.len() < AddressInfo::SIZE + 1 {
+            return Err(Error::from(HciError::new(HciErrorKind::InvalidLength)));
+        }
+        self.address_info.pack(&mut data[..AddressInfo::SIZE])?;
+        data[AddressInfo::SIZE] = u8::from(self.io_capability);
+        Ok(AddressInfo::SIZE + 1)
+    }
+}
+
+impl<'a> UnpackFixed<'a, AddressInfo, Error> for PairDevice {
+    fn unpack(data: &'a [u8]) -> Result<AddressInfo, Error> {
+        AddressInfo::unpack(data)
+    }
+}
+
+impl Command for PairDevice {
+    const OPERATION: OperationId = OperationId::PairDevice;
+}
+
+address_command!(
+    /// Cancel an ongoing pairing
+    ///
+    /// The reply holds the address of the device.
+    CancelPairDevice
+);
+
+/// Remove the keys of a paired device
+///
+/// The reply holds the address of the device.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct UnpairDevice {
+    pub address_info: AddressInfo,
+    /// Also terminate any connection with the device
+    pub disconnect: bool,
+}
+
+impl UnpairDevice {
+    /// Create a new command
+    pub fn new(address_info: AddressInfo, disconnect: bool) -> UnpairDevice {
+        UnpairDevice {
+            address_info,
+            disconnect,
+        }
+    }
+}
+
+impl Pack<UnpairDevice, Error> for UnpairDevice {
+    fn pack(&self, data: &mut [u8]) -> Result<usize, Error> {
+        if data.len() < AddressInfo::SIZE + 1 {
+            return Err(Error::from(HciError::new(HciErrorKind::InvalidLength)));
+        }
+        self.address_info.pack(&mut data[..AddressInfo::SIZE])?;
+        data[AddressInfo::SIZE] = u8::from(self.disconnect);
+        Ok(AddressInfo::SIZE + 1)
+    }
+}
+
+impl<'a> UnpackFixed<'a, AddressInfo, Error> for UnpairDevice {
+    fn unpack(data: &'a [u8]) -> Result<AddressInfo, Error> {
+        AddressInfo::unpack(data)
+    }
+}
+
+impl Command for UnpairDevice {
+    const OPERATION: OperationId = OperationId::UnpairDevice;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{events::CommandComplete, pack::Unpack, AddressType, HardwareAddress, Status};
+
+    #[test]
+    fn pack() {
+        let address_info = AddressInfo {
+            address: HardwareAddress::from([0x01, 0x02, 0x03, 0x04, 0x05, 0x06]),
+            address_type: AddressType::LePublic,
+        };
+        let mut data = [0u8; 8];
+        let command = PairDevice::new(address_info, IoCapability::KeyboardDisplay);
+        assert_eq!(command.pack(&mut data).unwrap(), 8);
+        assert_eq!(data, [0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x01, 0x04]);
+
+        let command = UnpairDevice::new(address_info, true);
+        assert_eq!(command.pack(&mut data).unwrap(), 8);
+        assert_eq!(data[7], 0x01);
+
+        let command = SetIoCapability::new(IoCapability::NoInputNoOutput);
+        assert_eq!(command.pack(&mut data).unwrap(), 1);
+        assert_eq!(data[0], 0x03);
+    }
+
+    #[test]
+    fn reply_status() {
+        let data = [0x19, 0x00, 0x05, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x00];
+        let (complete, _) = CommandComplete::unpack(&data).unwrap();
+        assert_eq!(complete.operation, OperationId::PairDevice);
+        match complete.parameters() {
+            Err(Error::Status(Status::AuthenticationFailed)) => (),
+            result => panic!("Unexpected result {:?}", result),
+        }
+        let address_info = PairDevice::unpack(complete.data).unwrap();
+        assert_eq!(address_info.address_type, AddressType::BrEdr);
+    }
+}
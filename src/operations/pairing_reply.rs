@@ -0,0 +1,207 @@
+use byteorder::{ByteOrder, LittleEndian};
+
+use crate::{
+    error::HciError,
+    error::HciErrorKind,
+    pack::{Pack, PackFixed, UnpackFixed},
+    AddressInfo, Error, OperationId,
+};
+
+use super::Command;
+
+/// Maximum length of a PIN code in octets
+pub const MAX_PIN_CODE_LENGTH: usize = 16;
+/// Maximum value of a passkey
+pub const MAX_PASSKEY: u32 = 999_999;
+
+/// Reply to a PIN code request with a PIN code
+///
+/// The reply holds the address of the device.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct PinCodeReply {
+    pub address_info: AddressInfo,
+    pin_length: u8,
+    pin_code: [u8; MAX_PIN_CODE_LENGTH],
+}
+
+impl PinCodeReply {
+    /// Create a new command, fails if the PIN code is empty or longer than 16
+    /// octets
+    pub fn new(address_info: AddressInfo, pin_code: &[u8]) -> Result<PinCodeReply, Error> {
+        if pin_code.is_empty() || pin_code.len() > MAX_PIN_CODE_LENGTH {
+            return Err(Error::from(HciError::new(HciErrorKind::InvalidLength)));
+        }
+        let mut code = [0u8; MAX_PIN_CODE_LENGTH];
+        code[..pin_code.len()].copy_from_slice(pin_code);
+        Ok(PinCodeReply {
+            address_info,
+            pin_length: pin_code.len() as u8,
+            pin_code: code,
+        })
+    }
+
+    /// PIN code to reply with
+    pub fn pin_code(&self) -> &[u8] {
+        &self.pin_code[..usize::from(self.pin_length)]
+    }
+}
+
+impl Pack<PinCodeReply, Error> for PinCodeReply {
+    fn pack(&self, data: &mut [u8]) -> Result<usize, Error> {
+        let size = AddressInfo::SIZE + 1 + MAX_PIN_CODE_LENGTH;
+        if data.len() < size {
+            return Err(Error::from(HciError::new(HciErrorKind::InvalidLength)));
+        }
+        self.address_info.pack(&mut data[..AddressInfo::SIZE])?;
+        data[AddressInfo::SIZE] = self.pin_length;
+        data[AddressInfo::SIZE + 1..size].copy_from_slice(&self.pin_code);
+        Ok(size)
+    }
+}
+
+impl<'a> UnpackFixed<'a, AddressInfo, Error> for PinCodeReply {
+    fn unpack(data: &'a [u8]) -> Result<AddressInfo, Error> {
+        AddressInfo::unpack(data)
+    }
+}
+
+impl Command for PinCodeReply {
+    const OPERATION: OperationId = OperationId::PinCodeReply;
+}
+
+address_command!(
+    /// Reject a PIN code request
+    PinCodeNegativeReply
+);
+
+address_command!(
+    /// Accept a user confirmation request
+    UserConfirmReply
+);
+
+address_command!(
+    /// Reject a user confirmation request
+    UserConfirmNegativeReply
+);
+
+/// Reply to a user passkey request with a passkey
+///
+/// The reply holds the address of the device.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct UserPasskeyReply {
+    pub address_info: AddressInfo,
+    passkey: u32,
+}
+
+impl UserPasskeyReply {
+    /// Create a new command, fails if the passkey is larger than 999999
+    pub fn new(address_info: AddressInfo, passkey: u32) -> Result<UserPasskeyReply, Error> {
+        if passkey > MAX_PASSKEY {
+            return Err(Error::from(HciError::new(HciErrorKind::InvalidValue)));
+        }
+        Ok(UserPasskeyReply {
+            address_info,
+            passkey,
+        })
+    }
+
+    /// Passkey to reply with
+    pub fn passkey(&self) -> u32 {
+        self.passkey
+    }
+}
+
+impl Pack<UserPasskeyReply, Error> for UserPasskeyReply {
+    fn pack(&self, data: &mut [u8]) -> Result<usize, Error> {
+        let size = AddressInfo::SIZE + 4;
+        if data.len() < size {
+            return Err(Error::from(HciError::new(HciErrorKind::InvalidLength)));
+        }
+        self.address_info.pack(&mut data[..AddressInfo::SIZE])?;
+        LittleEndian::write_u32(&mut data[AddressInfo::SIZE..size], self.passkey);
+        Ok(size)
+    }
+}
+
+impl<'a> UnpackFixed<'a, AddressInfo, Error> for UserPasskeyReply {
+    fn unpack(data: &'a [u8]) -> Result<AddressInfo, Error> {
+        AddressInfo::unpack(data)
+    }
+}
+
+impl Command for UserPasskeyReply {
+    const OPERATION: OperationId = OperationId::UserPasskeyReply;
+}
+
+address_command!(
+    /// Reject a user passkey request
+    UserPasskeyNegativeReply
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AddressType, HardwareAddress};
+
+    #[test]
+    fn pin_code_reply() {
+        let address_info = AddressInfo {
+            address: HardwareAddress::from([0x01, 0x02, 0x03, 0x04, 0x05, 0x06]),
+            address_type: AddressType::BrEdr,
+        };
+        let command = PinCodeReply::new(address_info, b"0000").unwrap();
+        assert_eq!(command.pin_code(), b"0000");
+        let mut data = [0xffu8; 24];
+        assert_eq!(command.pack(&mut data).unwrap(), 24);
+        assert_eq!(&data[..7], &[0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x00]);
+        assert_eq!(data[7], 4);
+        assert_eq!(&data[8..12], b"0000");
+        assert_eq!(&data[12..], &[0u8; 12]);
+        assert_eq!(
+            PinCodeReply::unpack(&[0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x00]).unwrap(),
+            address_info
+        );
+
+        assert!(PinCodeReply::new(address_info, &[0x30; 16]).is_ok());
+        assert!(PinCodeReply::new(address_info, &[0x30; 17]).is_err());
+        match PinCodeReply::new(address_info, &[]) {
+            Err(Error::Hci(HciError {
+                kind: HciErrorKind::InvalidLength,
+            })) => (),
+            result => panic!("Unexpected result {:?}", result),
+        }
+    }
+
+    #[test]
+    fn user_passkey_reply() {
+        let address_info = AddressInfo {
+            address: HardwareAddress::from([0x01, 0x02, 0x03, 0x04, 0x05, 0x06]),
+            address_type: AddressType::BrEdr,
+        };
+        let command = UserPasskeyReply::new(address_info, 999_999).unwrap();
+        let mut data = [0u8; 11];
+        assert_eq!(command.pack(&mut data).unwrap(), 11);
+        assert_eq!(&data[7..], &[0x3f, 0x42, 0x0f, 0x00]);
+        assert!(UserPasskeyReply::new(address_info, 1_000_000).is_err());
+    }
+
+    #[test]
+    fn negative_reply() {
+        let address_info = AddressInfo {
+            address: HardwareAddress::from([0x01, 0x02, 0x03, 0x04, 0x05, 0x06]),
+            address_type: AddressType::BrEdr,
+        };
+        let mut data = [0u8; 7];
+        let command = UserConfirmNegativeReply::new(address_info);
+        assert_eq!(command.pack(&mut data).unwrap(), 7);
+        assert_eq!(data, [0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x00]);
+        assert_eq!(
+            UserConfirmNegativeReply::unpack(&data).unwrap(),
+            address_info
+        );
+        assert_eq!(
+            <UserConfirmNegativeReply as Command>::OPERATION,
+            OperationId::UserConfirmNegativeReply
+        );
+    }
+}
@@ -0,0 +1,94 @@
+use std::convert::TryFrom;
+
+use crate::{
+    error::HciError,
+    error::HciErrorKind,
+    events::Settings,
+    pack::{Pack, UnpackFixed},
+    system, Error, OperationId,
+};
+
+use super::Command;
+
+extended_enum!(
+    Privacy, u8,
+    Disabled => 0x00,
+    Enabled => 0x01,
+    Limited => 0x02,
+);
+
+/// Set the LE privacy mode and the local identity resolving key
+///
+/// With limited privacy the identity address is used while discoverable.
+/// The command is rejected by the kernel while the controller is powered.
+/// The reply holds the current settings.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SetPrivacy {
+    pub privacy: Privacy,
+    pub irk: [u8; 16],
+}
+
+impl SetPrivacy {
+    /// Create a new command
+    pub fn new(privacy: Privacy, irk: [u8; 16]) -> SetPrivacy {
+        SetPrivacy { privacy, irk }
+    }
+
+    /// Create a new command with a random identity resolving key
+    pub fn with_random_irk(privacy: Privacy) -> Result<SetPrivacy, Error> {
+        Ok(SetPrivacy::new(privacy, SetPrivacy::random_irk()?))
+    }
+
+    /// Generate an identity resolving key using the random number generator
+    /// of the operating system
+    pub fn random_irk() -> Result<[u8; 16], Error> {
+        let mut irk = [0u8; 16];
+        system::random_bytes(&mut irk)?;
+        Ok(irk)
+    }
+}
+
+impl Pack<SetPrivacy, Error> for SetPrivacy {
+    fn pack(&self, data: &mut [u8]) -> Result<usize, Error> {
+        if data.len() < 17 {
+            return Err(Error::from(HciError::new(HciErrorKind::InvalidLength)));
+        }
+        data[0] = u8::from(self.privacy);
+        data[1..17].copy_from_slice(&self.irk);
+        Ok(17)
+    }
+}
+
+impl<'a> UnpackFixed<'a, Settings, Error> for SetPrivacy {
+    fn unpack(data: &'a [u8]) -> Result<Settings, Error> {
+        Settings::unpack(data)
+    }
+}
+
+impl Command for SetPrivacy {
+    const OPERATION: OperationId = OperationId::SetPrivacy;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pack_unpack() {
+        let command = SetPrivacy::with_random_irk(Privacy::Limited).unwrap();
+        let mut data = [0u8; 17];
+        assert_eq!(command.pack(&mut data).unwrap(), 17);
+        assert_eq!(data[0], 0x02);
+        assert_eq!(&data[1..], &command.irk);
+        assert_ne!(SetPrivacy::random_irk().unwrap(), command.irk);
+
+        let settings = SetPrivacy::unpack(&[0x01, 0x22, 0x00, 0x00]).unwrap();
+        assert_eq!(
+            settings,
+            Settings::POWERED | Settings::LOW_ENERGY | Settings::PRIVACY
+        );
+        // Bit 17, wideband speech, is not known
+        let settings = SetPrivacy::unpack(&[0x01, 0x02, 0x02, 0x00]).unwrap();
+        assert_eq!(settings, Settings::POWERED | Settings::LOW_ENERGY);
+    }
+}
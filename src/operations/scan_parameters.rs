@@ -0,0 +1,97 @@
+use std::time::Duration;
+
+use byteorder::{ByteOrder, LittleEndian};
+
+use crate::{error::HciError, error::HciErrorKind, pack::Pack, Error, OperationId};
+
+use super::{duration_to_units, units_to_duration, Command};
+
+/// Length of a scan interval and window unit, 0.625 ms
+const SCAN_UNIT_NANOS: u64 = 625_000;
+const MIN_SCAN_UNITS: u128 = 0x0004;
+const MAX_SCAN_UNITS: u128 = 0x4000;
+
+/// Set the LE scan interval and scan window used for background scanning
+///
+/// Both values are rounded to the nearest 0.625 ms.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SetScanParameters {
+    interval: u16,
+    window: u16,
+}
+
+impl SetScanParameters {
+    /// Create a new command
+    ///
+    /// Fails if the interval or the window is outside 2.5 ms to 10.24 s or if
+    /// the window is longer than the interval.
+    pub fn new(interval: Duration, window: Duration) -> Result<SetScanParameters, Error> {
+        let interval = duration_to_units(interval, SCAN_UNIT_NANOS);
+        let window = duration_to_units(window, SCAN_UNIT_NANOS);
+        let range = MIN_SCAN_UNITS..=MAX_SCAN_UNITS;
+        if !range.contains(&interval) || !range.contains(&window) || window > interval {
+            return Err(Error::from(HciError::new(HciErrorKind::InvalidValue)));
+        }
+        Ok(SetScanParameters {
+            interval: interval as u16,
+            window: window as u16,
+        })
+    }
+
+    /// Scan interval after rounding
+    pub fn interval(&self) -> Duration {
+        units_to_duration(u64::from(self.interval), SCAN_UNIT_NANOS)
+    }
+
+    /// Scan window after rounding
+    pub fn window(&self) -> Duration {
+        units_to_duration(u64::from(self.window), SCAN_UNIT_NANOS)
+    }
+}
+
+impl Pack<SetScanParameters, Error> for SetScanParameters {
+    fn pack(&self, data: &mut [u8]) -> Result<usize, Error> {
+        if data.len() < 4 {
+            return Err(Error::from(HciError::new(HciErrorKind::InvalidLength)));
+        }
+        LittleEndian::write_u16(&mut data[0..2], self.interval);
+        LittleEndian::write_u16(&mut data[2..4], self.window);
+        Ok(4)
+    }
+}
+
+impl Command for SetScanParameters {
+    const OPERATION: OperationId = OperationId::SetScanParameters;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new() {
+        let command =
+            SetScanParameters::new(Duration::from_millis(60), Duration::from_micros(30_300))
+                .unwrap();
+        assert_eq!(command.interval(), Duration::from_millis(60));
+        assert_eq!(command.window(), Duration::from_micros(30_000));
+        let mut data = [0u8; 4];
+        assert_eq!(command.pack(&mut data).unwrap(), 4);
+        assert_eq!(data, [0x60, 0x00, 0x30, 0x00]);
+
+        let command =
+            SetScanParameters::new(Duration::from_millis(10_240), Duration::from_micros(2_500))
+                .unwrap();
+        command.pack(&mut data).unwrap();
+        assert_eq!(data, [0x00, 0x40, 0x04, 0x00]);
+    }
+
+    #[test]
+    fn validate() {
+        let ms = Duration::from_millis;
+        assert!(SetScanParameters::new(ms(2), ms(2)).is_err());
+        assert!(SetScanParameters::new(ms(10_241), ms(10)).is_err());
+        assert!(SetScanParameters::new(ms(10), ms(20)).is_err());
+        assert!(SetScanParameters::new(ms(20), ms(20)).is_ok());
+    }
+}
@@ -0,0 +1,94 @@
+use crate::{
+    error::HciError,
+    error::HciErrorKind,
+    events::Settings,
+    pack::{Pack, UnpackFixed},
+    Error, HardwareAddress, OperationId,
+};
+
+use super::Command;
+
+/// Set the static random address used by LE only controllers
+///
+/// The command is rejected by the kernel while the controller is powered.
+/// The reply holds the current settings.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SetStaticAddress {
+    address: HardwareAddress,
+}
+
+impl SetStaticAddress {
+    /// Create a new command, fails if the address is not a valid static
+    /// random address
+    pub fn new(address: HardwareAddress) -> Result<SetStaticAddress, Error> {
+        if !address.is_static_random() {
+            return Err(Error::from(HciError::new(HciErrorKind::InvalidAddress)));
+        }
+        Ok(SetStaticAddress { address })
+    }
+
+    /// Create a command removing the static address
+    pub fn clear() -> SetStaticAddress {
+        SetStaticAddress {
+            address: HardwareAddress::from([0u8; 6]),
+        }
+    }
+
+    /// Address to set
+    pub fn address(&self) -> HardwareAddress {
+        self.address
+    }
+}
+
+impl Pack<SetStaticAddress, Error> for SetStaticAddress {
+    fn pack(&self, data: &mut [u8]) -> Result<usize, Error> {
+        if data.len() < 6 {
+            return Err(Error::from(HciError::new(HciErrorKind::InvalidLength)));
+        }
+        data[..6].copy_from_slice(&self.address.bytes());
+        Ok(6)
+    }
+}
+
+impl<'a> UnpackFixed<'a, Settings, Error> for SetStaticAddress {
+    fn unpack(data: &'a [u8]) -> Result<Settings, Error> {
+        Settings::unpack(data)
+    }
+}
+
+impl Command for SetStaticAddress {
+    const OPERATION: OperationId = OperationId::SetStaticAddress;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pack() {
+        let address = HardwareAddress::random_static().unwrap();
+        let command = SetStaticAddress::new(address).unwrap();
+        let mut data = [0u8; 6];
+        assert_eq!(command.pack(&mut data).unwrap(), 6);
+        assert_eq!(data, address.bytes());
+
+        assert!(
+            SetStaticAddress::new(HardwareAddress::from([0x01, 0x02, 0x03, 0x04, 0x05, 0x06]))
+                .is_err()
+        );
+        assert!(SetStaticAddress::new(HardwareAddress::from([0xff; 6])).is_err());
+        SetStaticAddress::clear().pack(&mut data).unwrap();
+        assert_eq!(data, [0u8; 6]);
+    }
+
+    #[test]
+    fn unpack_reply() {
+        // Bit 17, wideband speech, is not known
+        let settings = SetStaticAddress::unpack(&[0x01, 0x82, 0x02, 0x00]).unwrap();
+        assert_eq!(
+            settings,
+            Settings::POWERED | Settings::LOW_ENERGY | Settings::STATIC_ADDRESS
+        );
+        assert!(SetStaticAddress::unpack(&[0x01, 0x82, 0x02]).is_err());
+    }
+}
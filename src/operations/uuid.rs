@@ -0,0 +1,136 @@
+use crate::{
+    common::class_of_device::MajorServiceClass,
+    error::HciError,
+    error::HciErrorKind,
+    pack::{Pack, PackFixed, UnpackFixed},
+    ClassOfDevice, Error, OperationId, Uuid,
+};
+
+use super::Command;
+
+/// Add a UUID to the list of services provided by a controller
+///
+/// The service hint is merged into the major service classes of the class of
+/// device. Only the hints in the most significant octet of the class of
+/// device can be given, packing fails for others. The reply holds the
+/// resulting class of device.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AddUuid {
+    pub uuid: Uuid,
+    pub service_hint: MajorServiceClass,
+}
+
+impl AddUuid {
+    /// Create a new command
+    pub fn new(uuid: Uuid, service_hint: MajorServiceClass) -> AddUuid {
+        AddUuid { uuid, service_hint }
+    }
+}
+
+impl Pack<AddUuid, Error> for AddUuid {
+    fn pack(&self, data: &mut [u8]) -> Result<usize, Error> {
+        if data.len() < Uuid::SIZE + 1 {
+            return Err(Error::from(HciError::new(HciErrorKind::InvalidLength)));
+        }
+        if self.service_hint.bits() & 0xffff != 0 {
+            return Err(Error::from(HciError::new(HciErrorKind::InvalidValue)));
+        }
+        self.uuid.pack(&mut data[..Uuid::SIZE])?;
+        data[Uuid::SIZE] = (self.service_hint.bits() >> 16) as u8;
+        Ok(Uuid::SIZE + 1)
+    }
+}
+
+impl<'a> UnpackFixed<'a, ClassOfDevice, Error> for AddUuid {
+    fn unpack(data: &'a [u8]) -> Result<ClassOfDevice, Error> {
+        ClassOfDevice::unpack(data)
+    }
+}
+
+impl Command for AddUuid {
+    const OPERATION: OperationId = OperationId::AddUuid;
+}
+
+/// Remove a UUID from the list of services provided by a controller
+///
+/// The reply holds the resulting class of device.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RemoveUuid {
+    pub uuid: Uuid,
+}
+
+impl RemoveUuid {
+    /// Create a new command
+    pub fn new(uuid: Uuid) -> RemoveUuid {
+        RemoveUuid { uuid }
+    }
+
+    /// Create a command removing all UUIDs
+    pub fn all() -> RemoveUuid {
+        RemoveUuid {
+            uuid: Uuid::from(0u128),
+        }
+    }
+}
+
+impl Pack<RemoveUuid, Error> for RemoveUuid {
+    fn pack(&self, data: &mut [u8]) -> Result<usize, Error> {
+        if data.len() < Uuid::SIZE {
+            return Err(Error::from(HciError::new(HciErrorKind::InvalidLength)));
+        }
+        self.uuid.pack(&mut data[..Uuid::SIZE])?;
+        Ok(Uuid::SIZE)
+    }
+}
+
+impl<'a> UnpackFixed<'a, ClassOfDevice, Error> for RemoveUuid {
+    fn unpack(data: &'a [u8]) -> Result<ClassOfDevice, Error> {
+        ClassOfDevice::unpack(data)
+    }
+}
+
+impl Command for RemoveUuid {
+    const OPERATION: OperationId = OperationId::RemoveUuid;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pack() {
+        let command = AddUuid::new(
+            Uuid::from(0x110bu16),
+            MajorServiceClass::RENDERING | MajorServiceClass::AUDIO,
+        );
+        let mut data = [0u8; 17];
+        assert_eq!(command.pack(&mut data).unwrap(), 17);
+        assert_eq!(&data[12..14], &[0x0b, 0x11]);
+        assert_eq!(data[16], 0x24);
+
+        let command = AddUuid::new(
+            Uuid::from(0x110bu16),
+            MajorServiceClass::AUDIO | MajorServiceClass::LIMITED_DISCOVERABLE,
+        );
+        assert!(command.pack(&mut data).is_err());
+
+        let command = RemoveUuid::all();
+        assert_eq!(command.pack(&mut data).unwrap(), 16);
+        assert_eq!(&data[..16], &[0u8; 16]);
+    }
+
+    #[test]
+    fn unpack_reply() {
+        let class_of_device = AddUuid::unpack(&[0x0c, 0x02, 0x24]).unwrap();
+        assert_eq!(
+            class_of_device.major_service_class(),
+            MajorServiceClass::RENDERING | MajorServiceClass::AUDIO
+        );
+        let class_of_device = RemoveUuid::unpack(&[0x0c, 0x02, 0x00]).unwrap();
+        assert_eq!(
+            class_of_device.major_service_class(),
+            MajorServiceClass::empty()
+        );
+        assert!(RemoveUuid::unpack(&[0x0c, 0x02]).is_err());
+    }
+}
@@ -2,25 +2,72 @@ use std::os::unix::io::{AsRawFd, RawFd};
 
 use crate::error::{Error, HciError, HciErrorKind, Result};
 use crate::events::EventId;
+use crate::operations::Command;
 use crate::system;
 
 use byteorder::{ByteOrder, LittleEndian};
 
-const MGMT_BUFFER_SIZE: usize = 512;
 const MGMT_HEADER_SIZE: usize = 6;
+/// Frame size accepted by the kernel unless the MTU of the socket is raised
+const HCI_MAX_FRAME_SIZE: usize = 1028;
+/// Frame size requested from the kernel, the largest MTU that can be set
+const MGMT_MAX_FRAME_SIZE: usize = u16::MAX as usize;
+/// Maximum size of the parameters of a command
+pub(crate) const MGMT_MAX_PARAMETERS_SIZE: usize = MGMT_MAX_FRAME_SIZE - MGMT_HEADER_SIZE;
 
 /// HCI Socket can be used to communicate with the Linux kernel using the
 /// HCI protocol.
 pub struct Socket {
     socket: RawFd,
+    mtu: usize,
+    /// Frame buffer reused between commands, zeroed after each write
+    buffer: Vec<u8>,
+    /// Frame buffer reused between events, large enough for any frame
+    receive_buffer: Vec<u8>,
 }
 
 impl Socket {
     /// Create a new Socket
+    ///
+    /// The MTU of the socket is raised so that large commands, for example
+    /// loading many keys, fit in a single frame. Kernels not supporting this
+    /// keep the default of 1028 octets, larger commands then fail with
+    /// `InvalidLength`.
     pub fn new() -> Result<Socket> {
         let socket = system::hci_socket()?;
         system::bind_mgmn(socket)?;
-        Ok(Socket { socket })
+        let mtu = match system::set_send_mtu(socket, MGMT_MAX_FRAME_SIZE as u16) {
+            Ok(()) => MGMT_MAX_FRAME_SIZE,
+            Err(_) => HCI_MAX_FRAME_SIZE,
+        };
+        Ok(Socket {
+            socket,
+            mtu,
+            buffer: Vec::new(),
+            receive_buffer: Vec::new(),
+        })
+    }
+
+    /// Buffer for the parameters of a command, sized to the MTU of the socket
+    fn parameters_buffer(&mut self) -> &mut [u8] {
+        if self.buffer.len() != self.mtu {
+            self.buffer.resize(self.mtu, 0);
+        }
+        &mut self.buffer[MGMT_HEADER_SIZE..]
+    }
+
+    /// Write the header and send a frame with parameters of the given size
+    /// already placed in the buffer
+    fn write_frame(&mut self, opcode: u16, index: u16, size: usize) -> Result<usize> {
+        let end = MGMT_HEADER_SIZE + size;
+        LittleEndian::write_u16(&mut self.buffer[0..2], opcode);
+        LittleEndian::write_u16(&mut self.buffer[2..4], index);
+        LittleEndian::write_u16(&mut self.buffer[4..MGMT_HEADER_SIZE], size as u16);
+        let result = system::socket_write(self.socket, &self.buffer[..end]);
+        for b in self.buffer[..end].iter_mut() {
+            *b = 0;
+        }
+        Ok(result?)
     }
 
     pub fn send_command<T: Into<u16>>(
@@ -29,30 +76,51 @@ impl Socket {
         index: u16,
         data: &[u8],
     ) -> Result<usize> {
-        assert!(data.len() < u16::max_value() as usize);
-        let end = MGMT_HEADER_SIZE + data.len();
-        assert!(end < MGMT_BUFFER_SIZE);
-        let mut buffer = [0u8; MGMT_BUFFER_SIZE];
-        buffer[MGMT_HEADER_SIZE..end].copy_from_slice(data);
-        LittleEndian::write_u16(&mut buffer[0..2], opcode.into());
-        LittleEndian::write_u16(&mut buffer[2..4], index);
-        let size = data.len() as u16;
-        LittleEndian::write_u16(&mut buffer[4..MGMT_BUFFER_SIZE], size);
-        match system::socket_write(self.socket, &buffer[..end]) {
-            Ok(size) => Ok(size),
-            Err(err) => Err(err.into()),
+        let buffer = self.parameters_buffer();
+        if data.len() > buffer.len() {
+            return Err(Error::Hci(HciError::new(HciErrorKind::InvalidLength)));
         }
+        buffer[..data.len()].copy_from_slice(data);
+        self.write_frame(opcode.into(), index, data.len())
     }
 
+    /// Send a typed command to the controller with the given index
+    pub fn send<C: Command>(&mut self, index: u16, command: &C) -> Result<usize> {
+        let size = match command.pack(self.parameters_buffer()) {
+            Ok(size) => size,
+            Err(err) => {
+                // Packing may have failed after writing part of the command
+                for b in self.buffer.iter_mut() {
+                    *b = 0;
+                }
+                return Err(err);
+            }
+        };
+        self.write_frame(C::OPERATION.into(), index, size)
+    }
+
+    /// Receive an event, copying its parameters into `data`
+    ///
+    /// Fails with `InvalidLength` if the parameters do not fit in `data` and
+    /// with `NotEnoughData` if the frame is shorter than its header claims.
     pub fn receive_event(&mut self, data: &mut [u8]) -> Result<(usize, EventId, u16)> {
-        let mut buffer = [0u8; MGMT_BUFFER_SIZE];
-        let read = system::socket_read(self.socket, &mut buffer)?;
+        if self.receive_buffer.len() != MGMT_MAX_FRAME_SIZE {
+            self.receive_buffer.resize(MGMT_MAX_FRAME_SIZE, 0);
+        }
+        let buffer = &mut self.receive_buffer;
+        let read = system::socket_read(self.socket, buffer)?;
         if read < MGMT_HEADER_SIZE {
             return Err(Error::Hci(HciError::new(HciErrorKind::NotEnoughData)));
         }
         let event = EventId::from(LittleEndian::read_u16(&buffer[0..2]));
         let index = LittleEndian::read_u16(&buffer[2..4]);
         let size = LittleEndian::read_u16(&buffer[4..6]) as usize;
+        if size > read - MGMT_HEADER_SIZE {
+            return Err(Error::Hci(HciError::new(HciErrorKind::NotEnoughData)));
+        }
+        if size > data.len() {
+            return Err(Error::Hci(HciError::new(HciErrorKind::InvalidLength)));
+        }
         data[..size].copy_from_slice(&buffer[MGMT_HEADER_SIZE..MGMT_HEADER_SIZE + size]);
         Ok((size, event, index))
     }
@@ -1,4 +1,3 @@
-use libc;
 use std::io;
 use std::mem::size_of;
 use std::os::unix::io::RawFd;
@@ -65,6 +64,22 @@ pub(crate) fn bind_mgmn(socket: RawFd) -> io::Result<()> {
     bind_device(socket, MGMT_INDEX_NONE)
 }
 
+const SOL_BLUETOOTH: i32 = 274;
+const BT_SNDMTU: i32 = 12;
+
+/// Set the maximum size of a frame written to the socket
+pub(crate) fn set_send_mtu(socket: RawFd, mtu: u16) -> io::Result<()> {
+    let mtu_ptr: *const u16 = &mtu;
+    let _ = ccall!(libc::setsockopt(
+        socket,
+        SOL_BLUETOOTH,
+        BT_SNDMTU,
+        mtu_ptr as *const libc::c_void,
+        size_of::<u16>() as u32
+    ));
+    Ok(())
+}
+
 pub(crate) fn socket_write(socket: RawFd, buffer: &[u8]) -> io::Result<usize> {
     let buffer_ptr: *const [u8] = buffer;
     let buffer_ptr: *const core::ffi::c_void = buffer_ptr as *const core::ffi::c_void;
@@ -78,3 +93,16 @@ pub(crate) fn socket_read(socket: RawFd, buffer: &mut [u8]) -> io::Result<usize>
     let bytes = ccall!(libc::read(socket, buffer_ptr, buffer.len()));
     Ok(bytes as usize)
 }
+
+/// Fill the buffer with random data from the operating system
+pub(crate) fn random_bytes(buffer: &mut [u8]) -> io::Result<()> {
+    let mut offset = 0;
+    while offset < buffer.len() {
+        let remaining = &mut buffer[offset..];
+        let buffer_ptr: *mut [u8] = remaining;
+        let buffer_ptr: *mut core::ffi::c_void = buffer_ptr as *mut core::ffi::c_void;
+        let bytes = ccall!(libc::getrandom(buffer_ptr, remaining.len(), 0));
+        offset += bytes as usize;
+    }
+    Ok(())
+}
@@ -0,0 +1,199 @@
+use std::fmt;
+use std::str::FromStr;
+
+use byteorder::{ByteOrder, LittleEndian};
+
+use crate::{
+    error::HciError,
+    error::HciErrorKind,
+    pack::{PackFixed, UnpackFixed},
+    Error,
+};
+
+/// Bluetooth base UUID, 00000000-0000-1000-8000-00805F9B34FB
+const BASE_UUID: u128 = 0x0000_0000_0000_1000_8000_0080_5f9b_34fb;
+const BASE_UUID_MASK: u128 = 0x0000_0000_ffff_ffff_ffff_ffff_ffff_ffff;
+
+/// Universally Unique Identifier (UUID)
+///
+/// 16-bit and 32-bit UUIDs are stored expanded through the Bluetooth base
+/// UUID, so that the same UUID compares equal regardless of the form it was
+/// created from.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub struct Uuid {
+    value: u128,
+}
+
+impl Uuid {
+    /// Size of a packed UUID in octets
+    pub const SIZE: usize = 16;
+
+    /// Get as 128-bit value
+    pub fn as_u128(self) -> u128 {
+        self.value
+    }
+
+    /// Get as 32-bit value, if the UUID is derived from the base UUID
+    pub fn as_u32(self) -> Option<u32> {
+        if self.value & BASE_UUID_MASK == BASE_UUID {
+            Some((self.value >> 96) as u32)
+        } else {
+            None
+        }
+    }
+
+    /// Get as 16-bit value, if the UUID is derived from the base UUID
+    pub fn as_u16(self) -> Option<u16> {
+        match self.as_u32() {
+            Some(value) if value <= u32::from(u16::MAX) => Some(value as u16),
+            _ => None,
+        }
+    }
+
+    /// Get as little endian byte array, as used by the management interface
+    pub fn to_bytes_le(self) -> [u8; 16] {
+        self.value.to_le_bytes()
+    }
+}
+
+impl From<u16> for Uuid {
+    fn from(value: u16) -> Uuid {
+        Uuid::from(u32::from(value))
+    }
+}
+
+impl From<u32> for Uuid {
+    fn from(value: u32) -> Uuid {
+        Uuid {
+            value: u128::from(value) << 96 | BASE_UUID,
+        }
+    }
+}
+
+impl From<u128> for Uuid {
+    fn from(value: u128) -> Uuid {
+        Uuid { value }
+    }
+}
+
+impl fmt::Display for Uuid {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{:08x}-{:04x}-{:04x}-{:04x}-{:012x}",
+            (self.value >> 96) as u32,
+            (self.value >> 80) as u16,
+            (self.value >> 64) as u16,
+            (self.value >> 48) as u16,
+            self.value & 0xffff_ffff_ffff
+        )
+    }
+}
+
+impl FromStr for Uuid {
+    type Err = Error;
+
+    /// Parse a UUID, either as 4 or 8 hexadecimal digits or in the 128-bit
+    /// form 00000000-0000-0000-0000-000000000000
+    fn from_str(value: &str) -> Result<Uuid, Error> {
+        let invalid = || Error::from(HciError::new(HciErrorKind::InvalidValue));
+        if !value.chars().all(|c| c.is_ascii_hexdigit() || c == '-') {
+            return Err(invalid());
+        }
+        match value.len() {
+            4 => Ok(Uuid::from(
+                u16::from_str_radix(value, 16).map_err(|_| invalid())?,
+            )),
+            8 => Ok(Uuid::from(
+                u32::from_str_radix(value, 16).map_err(|_| invalid())?,
+            )),
+            36 => {
+                let groups: Vec<&str> = value.split('-').collect();
+                let lengths: Vec<usize> = groups.iter().map(|g| g.len()).collect();
+                if lengths != [8, 4, 4, 4, 12] {
+                    return Err(invalid());
+                }
+                let value = u128::from_str_radix(&groups.concat(), 16).map_err(|_| invalid())?;
+                Ok(Uuid::from(value))
+            }
+            _ => Err(invalid()),
+        }
+    }
+}
+
+impl PackFixed<Uuid, Error> for Uuid {
+    fn pack(&self, data: &mut [u8]) -> Result<(), Error> {
+        if data.len() != Uuid::SIZE {
+            return Err(Error::from(HciError::new(HciErrorKind::InvalidLength)));
+        }
+        data.copy_from_slice(&self.to_bytes_le());
+        Ok(())
+    }
+}
+
+impl<'a> UnpackFixed<'a, Uuid, Error> for Uuid {
+    /// Unpack a little endian 16-bit, 32-bit or 128-bit UUID
+    fn unpack(data: &'a [u8]) -> Result<Uuid, Error> {
+        match data.len() {
+            2 => Ok(Uuid::from(LittleEndian::read_u16(data))),
+            4 => Ok(Uuid::from(LittleEndian::read_u32(data))),
+            16 => Ok(Uuid::from(LittleEndian::read_u128(data))),
+            _ => Err(Error::from(HciError::new(HciErrorKind::InvalidLength))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn convert() {
+        let uuid = Uuid::from(0x180du16);
+        assert_eq!(uuid.as_u16(), Some(0x180d));
+        assert_eq!(uuid.as_u32(), Some(0x0000_180d));
+        assert_eq!(uuid, Uuid::from(0x0000_180du32));
+        assert_eq!(uuid.as_u128(), 0x0000_180d_0000_1000_8000_0080_5f9b_34fb);
+
+        let uuid = Uuid::from(0x1234_5678u32);
+        assert_eq!(uuid.as_u16(), None);
+        assert_eq!(uuid.as_u32(), Some(0x1234_5678));
+
+        let uuid = Uuid::from(0x6e40_0001_b5a3_f393_e0a9_e50e_24dc_ca9eu128);
+        assert_eq!(uuid.as_u32(), None);
+    }
+
+    #[test]
+    fn parse_display() {
+        let uuid: Uuid = "180d".parse().unwrap();
+        assert_eq!(uuid.to_string(), "0000180d-0000-1000-8000-00805f9b34fb");
+        let uuid: Uuid = "0000180D-0000-1000-8000-00805F9B34FB".parse().unwrap();
+        assert_eq!(uuid.as_u16(), Some(0x180d));
+        let uuid: Uuid = "6e400001-b5a3-f393-e0a9-e50e24dcca9e".parse().unwrap();
+        assert_eq!(uuid.to_string(), "6e400001-b5a3-f393-e0a9-e50e24dcca9e");
+
+        assert!("180".parse::<Uuid>().is_err());
+        assert!("+180".parse::<Uuid>().is_err());
+        assert!("6e400001b-5a3-f393-e0a9-e50e24dcca9e"
+            .parse::<Uuid>()
+            .is_err());
+    }
+
+    #[test]
+    fn pack_unpack() {
+        let uuid = Uuid::from(0x180du16);
+        let mut data = [0u8; 16];
+        uuid.pack(&mut data).unwrap();
+        assert_eq!(
+            data,
+            [
+                0xfb, 0x34, 0x9b, 0x5f, 0x80, 0x00, 0x00, 0x80, 0x00, 0x10, 0x00, 0x00, 0x0d, 0x18,
+                0x00, 0x00
+            ]
+        );
+        assert_eq!(Uuid::unpack(&data).unwrap(), uuid);
+        assert_eq!(Uuid::unpack(&[0x0d, 0x18]).unwrap(), uuid);
+        assert_eq!(Uuid::unpack(&[0x0d, 0x18, 0x00, 0x00]).unwrap(), uuid);
+        assert!(Uuid::unpack(&[0x0d, 0x18, 0x00]).is_err());
+    }
+}